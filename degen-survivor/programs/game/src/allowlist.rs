@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::state::*;
+
+/// Check a joining wallet against a private game's allowlist and access key.
+/// Open games (no root, no access key) accept everyone.
+pub fn require_game_access(
+    game_state: &GameState,
+    player: &Pubkey,
+    proof: &[[u8; 32]],
+    access_signer: Option<Pubkey>,
+) -> Result<()> {
+    if let Some(root) = game_state.allowlist_root {
+        require!(
+            proof.len() <= MAX_ALLOWLIST_PROOF_LEN,
+            GameError::NotOnAllowlist
+        );
+        require!(
+            verify_allowlist_proof(&root, player, proof),
+            GameError::NotOnAllowlist
+        );
+    }
+
+    // the key signed the transaction, knowing it never puts a secret on chain
+    if let Some(expected) = game_state.access_key {
+        require!(
+            access_signer == Some(expected),
+            GameError::InvalidAccessKey
+        );
+    }
+
+    Ok(())
+}
+
+/// Leaf of the allowlist tree, sha256 of the wallet address
+pub fn allowlist_leaf(player: &Pubkey) -> [u8; 32] {
+    hashv(&[player.as_ref()]).to_bytes()
+}
+
+/// Walk the proof up to the root, each pair is hashed in sorted order
+/// so the proof does not need left/right flags
+pub fn verify_allowlist_proof(root: &[u8; 32], player: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(allowlist_leaf(player), |node, sibling| {
+        if node <= *sibling {
+            hashv(&[node.as_ref(), sibling.as_ref()]).to_bytes()
+        } else {
+            hashv(&[sibling.as_ref(), node.as_ref()]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[a.as_ref(), b.as_ref()]).to_bytes()
+        } else {
+            hashv(&[b.as_ref(), a.as_ref()]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn every_member_of_a_four_leaf_tree_verifies() {
+        let players: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = players.iter().map(allowlist_leaf).collect();
+        let left = parent(leaves[0], leaves[1]);
+        let right = parent(leaves[2], leaves[3]);
+        let root = parent(left, right);
+
+        assert!(verify_allowlist_proof(&root, &players[0], &[leaves[1], right]));
+        assert!(verify_allowlist_proof(&root, &players[1], &[leaves[0], right]));
+        assert!(verify_allowlist_proof(&root, &players[2], &[leaves[3], left]));
+        assert!(verify_allowlist_proof(&root, &players[3], &[leaves[2], left]));
+    }
+
+    #[test]
+    fn outsiders_and_wrong_proofs_are_rejected() {
+        let players: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let root = parent(allowlist_leaf(&players[0]), allowlist_leaf(&players[1]));
+
+        let outsider = Pubkey::new_unique();
+        assert!(!verify_allowlist_proof(&root, &outsider, &[allowlist_leaf(&players[1])]));
+        assert!(!verify_allowlist_proof(&root, &players[0], &[]));
+        assert!(!verify_allowlist_proof(&root, &players[0], &[[0; 32]]));
+    }
+
+    #[test]
+    fn single_wallet_tree_needs_an_empty_proof() {
+        let player = Pubkey::new_unique();
+        let root = allowlist_leaf(&player);
+        assert!(verify_allowlist_proof(&root, &player, &[]));
+        assert!(!verify_allowlist_proof(&root, &player, &[root]));
+    }
+}
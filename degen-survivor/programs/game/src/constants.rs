@@ -5,24 +5,25 @@ pub const PLAYER_SEED: &[u8] = b"player";
 
 /// PDA seed for RoundResult
 pub const ROUND_RESULT_SEED: &[u8] = b"round-result";
-// 500 Degen 
-pub const DEFAULT_ENTRY_FEE : u64 = 500_000_000_000;
 
-pub const MAX_PLAYER : u16 = 50;
+/// PDA seed for GameTemplate
+pub const TEMPLATE_SEED: &[u8] = b"game-template";
 
-pub const MIN_PLAYER :u16= 2;
+/// PDA seed for Sponsorship
+pub const SPONSOR_SEED: &[u8] = b"sponsor";
 
-pub const TOTAL_ROUNDS :u8 = 5;
+/// PDA seed for PlayerProfile
+pub const PROFILE_SEED: &[u8] = b"profile";
+
+// 500 Degen 
+pub const DEFAULT_ENTRY_FEE : u64 = 500_000_000_000;
 
-pub const ROUND_DURATION_SECONDS :i64 = 60;
-// 2min gaps between 2 round 
-pub const ROUND_GAP_SECONDS :i64 = 120 ;
+// Game timing, player limits and fee come from the GameTemplate a game was created with
+// lowest min_player a template can set
+pub const MIN_PLAYER :u16= 2;
 
-/// Prediction lockout window (last N seconds, no submissions allowed)
-/// During the last 5 seconds, predictions are locked, so users cannot change or submit answers when prices or outcomes are almost known.
-pub const PREDICTION_LOCKOUT_SECONDS: i64 = 5;
-// platform fee in bias point (600 = 6%)
-pub const PLATFORM_FEE_BPS:u16 = 600;
+/// Most rounds a template can configure
+pub const MAX_ROUNDS: u8 = 20;
 
 pub const MAX_USERNAME_LENGTH : usize = 20;
 // point for correct answer
@@ -45,8 +46,46 @@ pub const PRIZE_RANK_4_5_BPS : u16 = 600;
 // 2%
 pub const PRIZE_RANK_6_10_BPS : u16 = 200;
 
+/// Prize places kept on the leaderboard while players are evaluated
+pub const LEADERBOARD_SIZE : usize = 10;
+
 /// Basis points divisor
 pub const BPS_DIVISOR: u64 = 10_000;
 
 /// Time before game start when registration closes (2 minutes)
-pub const REGISTRATION_CLOSE_BEFORE_START: i64 = 120;
\ No newline at end of file
+pub const REGISTRATION_CLOSE_BEFORE_START: i64 = 120;
+
+/// Max distance (seconds) between a price snapshot and the round boundary it prices
+pub const SNAPSHOT_TOLERANCE_SECONDS: i64 = 30;
+
+/// Max range zone width a template can set (1000 = 10%)
+/// ZoneA: < -width, ZoneB: [-width, 0), ZoneC: [0, width), ZoneD: >= width
+pub const MAX_RANGE_ZONE_WIDTH_BPS: u16 = 1000;
+
+/// Max crank reward per transition, in bps of the entry fee (50 = 0.5%)
+pub const MAX_CRANK_REWARD_BPS: u64 = 50;
+
+/// Grace period after the last round before anyone can complete the game (1 hour)
+pub const COMPLETE_GAME_GRACE_SECONDS: i64 = 60 * 60;
+
+/// Time an overdue transition waits before anyone can cancel the game (1 hour)
+/// covers games that can no longer start, advance or resolve
+pub const STUCK_GAME_TIMEOUT_SECONDS: i64 = 60 * 60;
+
+/// Time after round end players have to reveal committed predictions
+pub const REVEAL_WINDOW_SECONDS: i64 = 30;
+
+/// Max speed bonus on top of the points earned (10000 = +100%)
+pub const MAX_SPEED_BONUS_BPS: u16 = 10_000;
+
+/// Streak lengths with their own multiplier, longer streaks use the last one
+pub const STREAK_TIERS: usize = 4;
+
+/// Max streak multiplier (30000 = 3x)
+pub const MAX_STREAK_MULTIPLIER_BPS: u16 = 30_000;
+
+/// Deepest allowlist merkle proof accepted by join_game (~1M wallets)
+pub const MAX_ALLOWLIST_PROOF_LEN: usize = 20;
+
+/// Max platform fee a template can charge (1000 = 10%)
+pub const MAX_PLATFORM_FEE_BPS: u16 = 1000;
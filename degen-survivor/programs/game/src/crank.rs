@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::CrankRewardPaid;
+use crate::state::*;
+
+/// Pay the game's crank reward to a third party that drove a lifecycle
+/// transition. Creator cranks, games without a reward and callers that did
+/// not pass the token accounts are skipped. Returns the amount paid.
+pub fn pay_crank_reward<'info>(
+    game_state: &mut Account<'info, GameState>,
+    caller: &Signer<'info>,
+    prize_pool_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    caller_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_mint: Option<&InterfaceAccount<'info, Mint>>,
+    token_program: Option<&Interface<'info, TokenInterface>>,
+) -> Result<u64> {
+    if game_state.creator == caller.key() {
+        return Ok(0);
+    }
+
+    let reward = game_state.crank_reward.min(game_state.prize_pool);
+    if reward == 0 {
+        return Ok(0);
+    }
+
+    let (pool_account, caller_account, token_mint, token_program) =
+        match (prize_pool_token_account, caller_token_account, token_mint, token_program) {
+            (Some(pool), Some(to), Some(mint), Some(program)) => (pool, to, mint, program),
+            _ => return Ok(0),
+        };
+
+    require!(
+        token_mint.key() == game_state.token_mint
+            && caller_account.mint == game_state.token_mint
+            && pool_account.mint == game_state.token_mint,
+        GameError::InvalidTokenMint
+    );
+
+    // Pool is owned by the game_state PDA
+    let game_id_bytes = game_state.game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: pool_account.to_account_info(),
+                mint: token_mint.to_account_info(),
+                to: caller_account.to_account_info(),
+                authority: game_state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        reward,
+        token_mint.decimals,
+    )?;
+
+    game_state.prize_pool = game_state
+        .prize_pool
+        .checked_sub(reward)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    game_state.crank_paid = game_state
+        .crank_paid
+        .checked_add(reward)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Crank reward paid: {} to {}", reward, caller.key());
+
+    emit!(CrankRewardPaid {
+        game_id: game_state.game_id,
+        caller: caller.key(),
+        amount: reward,
+    });
+
+    Ok(reward)
+}
@@ -10,15 +10,6 @@ pub enum  GameError {
     #[msg("Game is full, maximum players reached")]
     GameFull,
     
-    #[msg("Game has already been completed")]
-    GameAlreadyCompleted,
-    
-    #[msg("Player has already joined this game")]
-    PlayerAlreadyJoined,
-    
-    #[msg("Player has not joined this game")]
-    PlayerNotInGame,
-
     #[msg("Player has already submitted prediction for this round")]
     AlreadyPredicted,
     
@@ -34,9 +25,6 @@ pub enum  GameError {
     #[msg("Round has not ended yet, cannot evaluate")]
     RoundNotEnded,
     
-    #[msg("Player did not make a prediction for this round")]
-    NoPredictionFound,
-    
     #[msg("This round has already been evaluated for this player")]
     AlreadyEvaluated,
     
@@ -78,5 +66,101 @@ pub enum  GameError {
     
     #[msg("Cannot join game, registration closes 2 minutes before start")]
     RegistrationClosed,
+
+    #[msg("Price snapshot is invalid for this round")]
+    InvalidPriceSnapshot,
+
+    #[msg("Required price snapshot was not provided")]
+    MissingPriceSnapshot,
+
+    #[msg("Round result has already been resolved")]
+    RoundAlreadyResolved,
+
+    #[msg("Round type cannot be resolved with the available price data")]
+    UnsupportedRoundType,
+
+    #[msg("Game cannot be cancelled by this signer at this time")]
+    CannotCancelGame,
+
+    #[msg("Entry fee has already been refunded")]
+    RefundAlreadyClaimed,
+
+    #[msg("Deadline has not passed yet, only the creator can crank this transition")]
+    DeadlineNotReached,
+
+    #[msg("Crank reward exceeds the allowed maximum")]
+    CrankRewardTooHigh,
+
+    #[msg("Token account mint does not match the game pool")]
+    InvalidTokenMint,
+
+    #[msg("Round has no correct answer yet")]
+    RoundNotResolved,
+
+    #[msg("Account is not a PlayerState of this game")]
+    InvalidPlayerAccount,
+
+    #[msg("Player has already been ranked")]
+    PlayerAlreadyRanked,
+
+    #[msg("Invalid game template configuration")]
+    InvalidTemplateConfig,
+
+    #[msg("Game template is not active")]
+    TemplateInactive,
+
+    #[msg("Game uses commit-reveal, submit a commitment instead")]
+    CommitRevealRequired,
+
+    #[msg("Game does not use commit-reveal")]
+    CommitRevealDisabled,
+
+    #[msg("Reveal window is not open")]
+    RevealWindowClosed,
+
+    #[msg("No commitment to reveal for this round")]
+    NothingToReveal,
+
+    #[msg("Revealed choice does not match commitment")]
+    InvalidReveal,
+
+    #[msg("Player has been eliminated")]
+    PlayerEliminated,
+
+    #[msg("Invalid elimination threshold or lives for game mode")]
+    InvalidEliminationThreshold,
+
+    #[msg("Invalid speed bonus configuration")]
+    InvalidSpeedBonus,
+
+    #[msg("Invalid streak multiplier table")]
+    InvalidStreakMultipliers,
+
+    #[msg("Wallet is not on the game allowlist")]
+    NotOnAllowlist,
+
+    #[msg("Join must be co-signed by the game access key")]
+    InvalidAccessKey,
+
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Treasury accounts missing or invalid")]
+    InvalidTreasuryAccount,
+
+    #[msg("Sponsorship already refunded")]
+    SponsorshipAlreadyRefunded,
+
+    #[msg("Game is not settled yet")]
+    GameNotSettled,
+
+    #[msg("Accounts of this game are still open")]
+    AccountsStillOpen,
+
+    #[msg("Account is not the PlayerProfile of this player")]
+    InvalidProfileAccount,
+
+    #[msg("Price snapshots of this game are still retained, the game cannot be closed yet")]
+    SnapshotsStillRetained,
 }
 
@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::STREAK_TIERS;
+use crate::state::{GameMode, GameType, PredectionChoice, RoundType, SpeedBonus};
+
+/// New game scheduled from a template
+#[event]
+pub struct GameCreated {
+    pub game_id: u64,
+    pub creator: Pubkey,
+    pub template: Pubkey,
+    pub game_type: GameType,
+    pub start_time: i64,
+    pub entry_fee: u64,
+    pub token_mint: Pubkey,
+    pub total_rounds: u8,
+    pub max_player: u16,
+    pub crank_reward: u64,
+    pub commit_reveal: bool,
+    pub game_mode: GameMode,
+    pub lives: u8,
+    pub speed_bonus: Option<SpeedBonus>,
+    pub streak_multipliers: Option<[u16; STREAK_TIERS]>,
+    pub private: bool,
+}
+
+/// Player paid the entry fee and joined
+#[event]
+pub struct PlayerJoined {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub username: String,
+    pub entry_slot: u64,
+    pub total_player: u16,
+    pub prize_pool: u64,
+}
+
+/// Player left before registration closed and got the entry fee back
+#[event]
+pub struct PlayerLeft {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub refund: u64,
+    pub total_player: u16,
+    pub prize_pool: u64,
+}
+
+/// Wallet added DEGEN to a game's prize pool
+#[event]
+pub struct PoolSponsored {
+    pub game_id: u64,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub total_sponsored: u64,
+    pub prize_pool: u64,
+}
+
+/// Admin set the prize a promoted game advertises
+#[event]
+pub struct GuaranteedPrizeSet {
+    pub game_id: u64,
+    pub amount: u64,
+}
+
+/// Treasury topped the pool up to the guarantee at start_game
+#[event]
+pub struct GuaranteeCovered {
+    pub game_id: u64,
+    pub amount: u64,
+    pub prize_pool: u64,
+}
+
+/// Treasury cover of a cancelled game sent back to the vault treasury at close_game
+#[event]
+pub struct TreasuryCoverReturned {
+    pub game_id: u64,
+    pub amount: u64,
+}
+
+/// Sponsor top-up returned from a cancelled game
+#[event]
+pub struct SponsorshipRefunded {
+    pub game_id: u64,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
+
+/// Game moved from Pending to Active
+#[event]
+pub struct GameStarted {
+    pub game_id: u64,
+    pub started_by: Pubkey,
+    pub total_player: u16,
+    pub prize_pool: u64,
+    pub timestamp: i64,
+}
+
+/// A round opened for predictions (round 1 at start_game, later ones at advance_round)
+#[event]
+pub struct RoundStarted {
+    pub game_id: u64,
+    pub round_number: u8,
+    pub round_type: RoundType,
+    pub start_price_btc: Option<u64>,
+    pub start_price_sol: Option<u64>,
+    pub round_start_ts: i64,
+    pub round_end_ts: i64,
+    pub magnitude_buckets: Option<[u64; 3]>,
+    pub range_zones: Option<[u64; 3]>,
+}
+
+/// Player locked in a prediction
+#[event]
+pub struct PredictionSubmitted {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub round_number: u8,
+    pub choice: PredectionChoice,
+    pub response_time: u32,
+    pub submitted_at: i64,
+}
+
+/// Player committed hash(choice || salt || player || game_id || round) in a commit-reveal game
+#[event]
+pub struct PredictionCommitted {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub round_number: u8,
+    pub commitment: [u8; 32],
+    pub response_time: u32,
+    pub submitted_at: i64,
+}
+
+/// Committed prediction opened after the round ended
+#[event]
+pub struct PredictionRevealed {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub round_number: u8,
+    pub choice: PredectionChoice,
+}
+
+/// Correct answer set for a round
+#[event]
+pub struct RoundResolved {
+    pub game_id: u64,
+    pub round_number: u8,
+    pub correct_answer: PredectionChoice,
+    pub price_change_btc: i64,
+    pub price_change_sol: i64,
+    pub timestamp: i64,
+}
+
+/// Player scored for a round, points is None when they did not predict
+#[event]
+pub struct PlayerEvaluated {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub round_number: u8,
+    pub points: Option<u16>,
+    pub speed_bonus: u16,
+    pub streak: u8,
+    pub total_score: u16,
+    pub lives_remaining: u8,
+    pub eliminated: bool,
+}
+
+/// Survivor: player knocked out of the game
+#[event]
+pub struct PlayerEliminated {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub round_number: u8,
+}
+
+/// All rounds played and resolved
+#[event]
+pub struct GameCompleted {
+    pub game_id: u64,
+    pub completed_by: Pubkey,
+    pub total_player: u16,
+    pub prize_pool: u64,
+    pub timestamp: i64,
+}
+
+/// Player placed on the leaderboard
+#[event]
+pub struct PlayerRanked {
+    pub game_id: u64,
+    pub player: Pubkey,
+    // None outside the prize places
+    pub rank: Option<u16>,
+    pub total_score: u16,
+    pub prize_amount: u64,
+}
+
+/// Every player has been ranked
+#[event]
+pub struct LeaderboardFinalized {
+    pub game_id: u64,
+    pub ranked_players: u16,
+    pub top_scorer: Option<Pubkey>,
+    pub higest_score: u16,
+}
+
+/// Winner paid from the game pool
+#[event]
+pub struct PrizeClaimed {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub rank: Option<u16>,
+    pub amount: u64,
+}
+
+/// Game cancelled before it started, or stuck while active
+#[event]
+pub struct GameCancelled {
+    pub game_id: u64,
+    pub cancelled_by: Pubkey,
+    pub total_player: u16,
+    pub prize_pool: u64,
+    pub refund_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Entry fee returned from a cancelled game
+#[event]
+pub struct RefundClaimed {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+/// Reward paid to a third party for cranking a transition
+#[event]
+pub struct CrankRewardPaid {
+    pub game_id: u64,
+    pub caller: Pubkey,
+    pub amount: u64,
+}
+
+/// Settled game closed, leftover pool swept to the platform
+#[event]
+pub struct GameClosed {
+    pub game_id: u64,
+    pub closed_by: Pubkey,
+    pub swept_amount: u64,
+    pub returned_to_treasury: u64,
+    pub timestamp: i64,
+}
+
+/// Game template created or changed
+#[event]
+pub struct TemplateUpdated {
+    pub template_id: u64,
+    pub authority: Pubkey,
+    pub active: bool,
+    pub total_rounds: u8,
+    pub round_duration_seconds: i64,
+    pub max_player: u16,
+    pub min_player: u16,
+    pub platform_fee_bps: u16,
+}
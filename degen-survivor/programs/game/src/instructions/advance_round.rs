@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use oracle::constants::PRICE_SNAPSHOT_SEED;
+use oracle::state::{AssetType, PriceSnapshot, SnapshotType};
 
 use crate::constants::*;
+use crate::crank::pay_crank_reward;
 use crate::errors::GameError;
+use crate::events::RoundStarted;
+use crate::instructions::resolve_round::{read_start_price, require_priced_assets, round_magnitude_buckets, round_range_zones};
 use crate::state::*;
 
 
@@ -9,28 +15,79 @@ use crate::state::*;
 #[instruction(game_id: u64, next_round: u8)]
 
 pub struct  AdvanceRound<'info> {
+    /// Creator, or anyone once the next round's scheduled start has passed
     #[account(mut)]
-    pub creator : Signer<'info>,
+    pub caller : Signer<'info>,
     #[account(
         mut,
         seeds = [GAME_SEED,game_id.to_le_bytes().as_ref()],
         bump = game_state.bump,
-        constraint = game_state.creator == creator.key() @ GameError::Unauthorized
     )]
     pub game_state : Account<'info,GameState>,
+    /// RoundResult of the round being closed
+    #[account(
+        seeds = [ROUND_RESULT_SEED,game_id.to_le_bytes().as_ref() , &[game_state.current_round]],
+        bump = current_round_result.bump,
+    )]
+    pub current_round_result : Account<'info,RoundResult>,
     #[account(
         init , 
-        payer = creator,
+        payer = caller,
         space = RoundResult::SIZE,
         seeds = [ROUND_RESULT_SEED,game_id.to_le_bytes().as_ref() , &[next_round]],
         bump
     )]
     pub next_round_result : Account<'info,RoundResult>,
 
+    /// BTC START snapshot for the next round
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[next_round],
+            &[AssetType::BTC as u8],
+            &[SnapshotType::START as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = btc_start_snapshot.bump,
+    )]
+    pub btc_start_snapshot : Option<Account<'info,PriceSnapshot>>,
+
+    /// SOL START snapshot for the next round
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[next_round],
+            &[AssetType::SOL as u8],
+            &[SnapshotType::START as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = sol_start_snapshot.bump,
+    )]
+    pub sol_start_snapshot : Option<Account<'info,PriceSnapshot>>,
+
+    /// Prize pool token account (only needed to pay the crank reward)
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+    )]
+    pub prize_pool_token_account : Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Caller's token account receiving the crank reward
+    #[account(mut)]
+    pub caller_token_account : Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint the game is played in (needed for token transfers)
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint : Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program : Option<Interface<'info, TokenInterface>>,
+
     pub system_program : Program<'info,System>,
 }
 
-pub fn handler(ctx:Context<AdvanceRound>,game_id:u64,next_round:u8,start_price_btc:Option<u64>,start_price_sol:Option<u64>)->Result<()>{
+pub fn handler(ctx:Context<AdvanceRound>,game_id:u64,next_round:u8)->Result<()>{
 
     let game_state = &mut ctx.accounts.game_state;
     let next_round_result = &mut ctx.accounts.next_round_result;
@@ -45,7 +102,29 @@ pub fn handler(ctx:Context<AdvanceRound>,game_id:u64,next_round:u8,start_price_b
     require!(next_round == game_state.current_round+1,GameError::InvalidRoundNumber);
 
     // check for last round 
-    require!(next_round<=TOTAL_ROUNDS,GameError::InvalidRoundNumber);
+    require!(next_round<=game_state.total_round,GameError::InvalidRoundNumber);
+
+    // current round must be over before the next one opens
+    require!(
+        clock.unix_timestamp > ctx.accounts.current_round_result.round_end_ts,
+        GameError::RoundNotEnded
+    );
+
+    // non-creator cranks wait for the scheduled start of the next round
+    if game_state.creator != ctx.accounts.caller.key() {
+        let scheduled_start = game_state.round_deadline[(next_round - 1) as usize]
+            .checked_sub(game_state.round_duration_seconds)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        require!(
+            clock.unix_timestamp >= scheduled_start,
+            GameError::DeadlineNotReached
+        );
+    }
+
+    // start prices come from the oracle
+    let start_price_btc = read_start_price(ctx.accounts.btc_start_snapshot.as_deref(), clock.unix_timestamp)?;
+    let start_price_sol = read_start_price(ctx.accounts.sol_start_snapshot.as_deref(), clock.unix_timestamp)?;
+    require_priced_assets(game_state.game_type, start_price_btc.is_some(), start_price_sol.is_some())?;
 
     // update game state 
     game_state.current_round = next_round;
@@ -54,21 +133,35 @@ pub fn handler(ctx:Context<AdvanceRound>,game_id:u64,next_round:u8,start_price_b
     next_round_result.round_number = next_round;
     next_round_result.round_type = game_state.round_types[(next_round - 1) as usize];
     next_round_result.start_price_btc = start_price_btc;
+    next_round_result.mid_price_btc = None;
     next_round_result.end_price_btc = None;
     next_round_result.start_price_sol = start_price_sol;
+    next_round_result.mid_price_sol = None;
     next_round_result.end_price_sol = None;
     next_round_result.price_change_btc = 0;
     next_round_result.price_change_sol = 0;
     next_round_result.correct_answer = None;
     next_round_result.round_start_ts = clock.unix_timestamp;
-    next_round_result.round_end_ts = clock.unix_timestamp + ROUND_DURATION_SECONDS;
+    next_round_result.round_end_ts = clock.unix_timestamp + game_state.round_duration_seconds;
     next_round_result.evaluation_ts = None;
     next_round_result.total_predection = 0;
     next_round_result.correct_predection = 0;
     next_round_result.partial_correct = 0;
     next_round_result.wrong_predection = 0;
+    next_round_result.knocked_out = 0;
+    next_round_result.magnitude_buckets = round_magnitude_buckets(next_round_result.round_type, game_state);
+    next_round_result.range_zones = round_range_zones(next_round_result.round_type, game_state, start_price_btc, start_price_sol);
     next_round_result.bump = ctx.bumps.next_round_result;
+    next_round_result.payer = ctx.accounts.caller.key();
 
+    pay_crank_reward(
+        game_state,
+        &ctx.accounts.caller,
+        ctx.accounts.prize_pool_token_account.as_ref(),
+        ctx.accounts.caller_token_account.as_ref(),
+        ctx.accounts.token_mint.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
 
     msg!("Round advanced!");
     msg!("Game ID: {}", game_id);
@@ -76,5 +169,17 @@ pub fn handler(ctx:Context<AdvanceRound>,game_id:u64,next_round:u8,start_price_b
     msg!("Start price BTC: {:?}", start_price_btc);
     msg!("Start price SOL: {:?}", start_price_sol);
 
+    emit!(RoundStarted {
+        game_id,
+        round_number: next_round,
+        round_type: next_round_result.round_type,
+        start_price_btc,
+        start_price_sol,
+        round_start_ts: next_round_result.round_start_ts,
+        round_end_ts: next_round_result.round_end_ts,
+        magnitude_buckets: next_round_result.magnitude_buckets,
+        range_zones: next_round_result.range_zones,
+    });
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::GameCancelled;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CancelGame<'info> {
+    /// Game creator, or anyone once the game failed to fill up or got stuck
+    pub authority: Signer<'info>,
+
+    /// GameState account
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Active games: result of the current round, to check it is stuck
+    #[account(
+        seeds = [ROUND_RESULT_SEED, game_id.to_le_bytes().as_ref(), &[game_state.current_round]],
+        bump = current_round_result.bump,
+    )]
+    pub current_round_result: Option<Account<'info, RoundResult>>,
+}
+
+pub fn handler(ctx: Context<CancelGame>, game_id: u64) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let can_cancel = match game_state.status {
+        GameStatus::Pending => {
+            let is_creator = game_state.creator == ctx.accounts.authority.key();
+            can_cancel_pending_game(game_state, is_creator, now)?
+        }
+
+        // Started games can only be cancelled once they stopped moving
+        GameStatus::Active => match ctx.accounts.current_round_result.as_deref() {
+            Some(round_result) => is_active_game_stuck(game_state, round_result, now)?,
+            None => false,
+        },
+
+        GameStatus::Completed | GameStatus::Cancelled => {
+            return err!(GameError::InvalidGameStatus);
+        }
+    };
+
+    require!(can_cancel, GameError::CannotCancelGame);
+
+    game_state.refund_bps = refund_bps(game_state)?;
+    game_state.status = GameStatus::Cancelled;
+    game_state.end_time = Some(now);
+
+    msg!("Game cancelled!");
+    msg!("Game ID: {}", game_id);
+    msg!("Cancelled by: {}", ctx.accounts.authority.key());
+    msg!("Players to refund: {}", game_state.total_player);
+    msg!("Prize pool: {}", game_state.prize_pool);
+    msg!("Refund share (bps): {}", game_state.refund_bps);
+
+    emit!(GameCancelled {
+        game_id,
+        cancelled_by: ctx.accounts.authority.key(),
+        total_player: game_state.total_player,
+        prize_pool: game_state.prize_pool,
+        refund_bps: game_state.refund_bps,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Creator can cancel any time before start, everyone else only once start
+/// time passed without enough players, or start_game could not run for
+/// STUCK_GAME_TIMEOUT_SECONDS (e.g. no usable start price)
+fn can_cancel_pending_game(game_state: &GameState, is_creator: bool, now: i64) -> Result<bool> {
+    let underfilled = now > game_state.start_time
+        && game_state.total_player < game_state.min_player;
+    let start_overdue = now
+        > game_state.start_time
+            .checked_add(STUCK_GAME_TIMEOUT_SECONDS)
+            .ok_or(GameError::ArithmeticOverflow)?;
+    Ok(is_creator || underfilled || start_overdue)
+}
+
+/// An active game is stuck once its next transition is overdue by
+/// STUCK_GAME_TIMEOUT_SECONDS: the current round was never resolved, or the
+/// next round was never opened. A resolved last round can always be completed.
+fn is_active_game_stuck(game_state: &GameState, round_result: &RoundResult, now: i64) -> Result<bool> {
+    let overdue_since = if round_result.correct_answer.is_none() {
+        round_result.round_end_ts
+    } else if game_state.current_round < game_state.total_round {
+        // anyone can open the next round from its scheduled start
+        let scheduled_start = game_state.round_deadline[game_state.current_round as usize]
+            .checked_sub(game_state.round_duration_seconds)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        round_result.round_end_ts.max(scheduled_start)
+    } else {
+        return Ok(false);
+    };
+
+    let cancel_after = overdue_since
+        .checked_add(STUCK_GAME_TIMEOUT_SECONDS)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    Ok(now > cancel_after)
+}
+
+/// Share of every entry fee and sponsorship the pool can still return.
+/// Crank rewards already left the pool and the treasury cover goes back to
+/// the treasury at close_game, so refunds are scaled down when the pool fell short.
+fn refund_bps(game_state: &GameState) -> Result<u16> {
+    let owed = game_state.prize_pool
+        .checked_add(game_state.crank_paid)
+        .ok_or(GameError::ArithmeticOverflow)?
+        .saturating_sub(game_state.treasury_cover);
+
+    if owed == 0 || game_state.prize_pool >= owed {
+        return Ok(BPS_DIVISOR as u16);
+    }
+
+    let share = (game_state.prize_pool as u128)
+        .checked_mul(BPS_DIVISOR as u128)
+        .ok_or(GameError::ArithmeticOverflow)?
+        / owed as u128;
+    Ok(share as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(round_end_ts: i64, resolved: bool) -> RoundResult {
+        let mut round_result = RoundResult::test_round(RoundType::PriceDirection);
+        round_result.round_end_ts = round_end_ts;
+        round_result.correct_answer = resolved.then_some(PredectionChoice::Up);
+        round_result
+    }
+
+    #[test]
+    fn creator_can_cancel_a_pending_game_any_time() {
+        let game = GameState::test_game();
+        assert!(can_cancel_pending_game(&game, true, game.start_time - 1_000).unwrap());
+        assert!(!can_cancel_pending_game(&game, false, game.start_time - 1_000).unwrap());
+    }
+
+    #[test]
+    fn anyone_can_cancel_an_underfilled_game_after_start() {
+        let mut game = GameState::test_game();
+        game.total_player = game.min_player - 1;
+        assert!(!can_cancel_pending_game(&game, false, game.start_time).unwrap());
+        assert!(can_cancel_pending_game(&game, false, game.start_time + 1).unwrap());
+
+        game.total_player = game.min_player;
+        assert!(!can_cancel_pending_game(&game, false, game.start_time + 1).unwrap());
+    }
+
+    #[test]
+    fn anyone_can_cancel_a_filled_game_that_never_started() {
+        let mut game = GameState::test_game();
+        game.total_player = game.min_player;
+        let overdue = game.start_time + STUCK_GAME_TIMEOUT_SECONDS;
+        assert!(!can_cancel_pending_game(&game, false, overdue).unwrap());
+        assert!(can_cancel_pending_game(&game, false, overdue + 1).unwrap());
+    }
+
+    #[test]
+    fn unresolved_round_is_stuck_after_the_timeout() {
+        let mut game = GameState::test_game();
+        game.current_round = 1;
+        let round_result = round(1_000, false);
+        let overdue = 1_000 + STUCK_GAME_TIMEOUT_SECONDS;
+        assert!(!is_active_game_stuck(&game, &round_result, overdue).unwrap());
+        assert!(is_active_game_stuck(&game, &round_result, overdue + 1).unwrap());
+    }
+
+    #[test]
+    fn next_round_is_overdue_from_its_scheduled_start() {
+        let mut game = GameState::test_game();
+        game.current_round = 1;
+        let round_result = round(game.round_deadline[0], true);
+        let next_start = game.round_deadline[1] - game.round_duration_seconds;
+        let overdue = next_start + STUCK_GAME_TIMEOUT_SECONDS;
+        assert!(!is_active_game_stuck(&game, &round_result, overdue).unwrap());
+        assert!(is_active_game_stuck(&game, &round_result, overdue + 1).unwrap());
+    }
+
+    #[test]
+    fn resolved_last_round_is_never_stuck() {
+        let mut game = GameState::test_game();
+        game.current_round = game.total_round;
+        let round_result = round(game.round_deadline[4], true);
+        assert!(!is_active_game_stuck(&game, &round_result, i64::MAX / 2).unwrap());
+    }
+
+    #[test]
+    fn untouched_pool_refunds_in_full() {
+        let mut game = GameState::test_game();
+        game.prize_pool = 1_000;
+        assert_eq!(refund_bps(&game).unwrap(), BPS_DIVISOR as u16);
+
+        game.prize_pool = 0;
+        assert_eq!(refund_bps(&game).unwrap(), BPS_DIVISOR as u16);
+    }
+
+    #[test]
+    fn treasury_cover_is_not_owed_to_players_or_sponsors() {
+        let mut game = GameState::test_game();
+        // 600 entries + 200 sponsored + 400 treasury cover, 100 paid to cranks
+        game.treasury_cover = 400;
+        game.crank_paid = 100;
+        game.prize_pool = 1_100;
+        game.refund_bps = refund_bps(&game).unwrap();
+        assert_eq!(game.refund_bps, BPS_DIVISOR as u16);
+
+        let refunded = game.refund_share(600).unwrap() + game.refund_share(200).unwrap();
+        assert_eq!(refunded, 800);
+        // the rest goes back to the treasury at close_game
+        assert_eq!(game.prize_pool - refunded, 300);
+    }
+
+    #[test]
+    fn sponsors_share_a_short_pool_with_players() {
+        let mut game = GameState::test_game();
+        // 600 entries + 400 sponsored, 200 paid to cranks
+        game.crank_paid = 200;
+        game.prize_pool = 800;
+        game.refund_bps = refund_bps(&game).unwrap();
+        assert_eq!(game.refund_share(600).unwrap(), 480);
+        assert_eq!(game.refund_share(400).unwrap(), 320);
+    }
+
+    #[test]
+    fn crank_rewards_scale_refunds_down() {
+        let mut game = GameState::test_game();
+        // 1_000 paid in, 100 went to cranks
+        game.prize_pool = 900;
+        game.crank_paid = 100;
+        game.refund_bps = refund_bps(&game).unwrap();
+        assert_eq!(game.refund_bps, 9_000);
+        assert_eq!(game.refund_share(500).unwrap(), 450);
+    }
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::constants::*;
 use crate::errors::GameError;
+use crate::events::PrizeClaimed;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -26,12 +28,41 @@ pub struct ClaimPrize<'info> {
         constraint = player_state.player == player.key() @ GameError::Unauthorized,
     )]
     pub player_state: Account<'info, PlayerState>,
-    
-    // Note: Prize distribution will be handled by Prize Distributor program
-    // via CPI. For now, we just mark as claimed.
+
+    /// PlayerProfile account
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// Mint the game is played in
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Prize pool token account (source of the prize)
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+        token::mint = token_mint,
+        token::authority = game_state,
+    )]
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Player's DEGEN token account (receives the prize)
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = player,
+    )]
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<ClaimPrize>, _game_id: u64) -> Result<()> {
+pub fn handler(ctx: Context<ClaimPrize>, game_id: u64) -> Result<()> {
     let game_state = &ctx.accounts.game_state;
     let player_state = &mut ctx.accounts.player_state;
     
@@ -47,29 +78,52 @@ pub fn handler(ctx: Context<ClaimPrize>, _game_id: u64) -> Result<()> {
         GameError::LeaderboardNotFinalized
     );
     
-    // Validate player has a rank
-    let rank = player_state.final_rank
-        .ok_or(GameError::LeaderboardNotFinalized)?;
-    
-    // Validate player is a winner (top 10)
-    require!(rank <= 10, GameError::NotAWinner);
-    
     // Validate not already claimed
     require!(
         !player_state.prize_claimed,
         GameError::PrizeAlreadyClaimed
     );
-    
-    // Mark as claimed
+
+    // Prize set at finalize_leaderboard: top places in Classic,
+    // an equal share for the last survivors in Survivor
+    let prize_amount = player_state.prize_amount;
+    require!(prize_amount > 0, GameError::NotAWinner);
+
+    // Mark as claimed before paying out
     player_state.prize_claimed = true;
-    
-    // TODO: Add CPI to Prize Distributor program here
-    // For now, just mark as claimed
+    ctx.accounts.player_profile.record_prize(prize_amount)?;
+
+    // Pool is owned by the game_state PDA
+    let game_id_bytes = game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.prize_pool_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: game_state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        prize_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
     
     msg!("Prize claimed!");
     msg!("Player: {}", player_state.player);
-    msg!("Rank: {}", rank);
+    msg!("Rank: {:?}", player_state.final_rank);
     msg!("Prize amount: {}", player_state.prize_amount);
+
+    emit!(PrizeClaimed {
+        game_id,
+        player: player_state.player,
+        rank: player_state.final_rank,
+        amount: prize_amount,
+    });
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::RefundClaimed;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ClaimRefund<'info> {
+    /// Player getting the entry fee back
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// GameState account (signs the transfer out of the pool)
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// PlayerState account
+    #[account(
+        mut,
+        seeds = [PLAYER_SEED, game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        constraint = player_state.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Mint the game is played in
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Prize pool token account holding the entry fees
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+        token::mint = token_mint,
+        token::authority = game_state,
+    )]
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Player's DEGEN token account
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = player,
+    )]
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<ClaimRefund>, game_id: u64) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let player_state = &ctx.accounts.player_state;
+
+    require!(
+        game_state.status == GameStatus::Cancelled,
+        GameError::InvalidGameStatus
+    );
+
+    require!(
+        !player_state.refund_claimed,
+        GameError::RefundAlreadyClaimed
+    );
+
+    // games cancelled mid-play may have paid crank rewards out of the pool
+    let refund_amount = game_state.refund_share(player_state.entry_paid)?;
+
+    // Pool is owned by the game_state PDA
+    let game_id_bytes = game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.prize_pool_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.game_state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        refund_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let game_state = &mut ctx.accounts.game_state;
+    let player_state = &mut ctx.accounts.player_state;
+
+    player_state.refund_claimed = true;
+    game_state.prize_pool = game_state
+        .prize_pool
+        .checked_sub(refund_amount)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Entry fee refunded!");
+    msg!("Game ID: {}", game_id);
+    msg!("Player: {}", player_state.player);
+    msg!("Refund: {}", refund_amount);
+    msg!("Remaining pool: {}", game_state.prize_pool);
+
+    emit!(RefundClaimed {
+        game_id,
+        player: player_state.player,
+        amount: refund_amount,
+    });
+
+    Ok(())
+}
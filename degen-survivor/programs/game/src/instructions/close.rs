@@ -0,0 +1,510 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_2022_extensions::transfer_fee::{harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint};
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+use oracle::constants::SNAPSHOT_RETENTION_SECONDS;
+use vault::constants::GLOBAL_VAULT_SEED;
+use vault::program::Vault;
+use vault::state::{GlobalVault, Treasury};
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::GameClosed;
+use crate::state::*;
+use crate::treasury::return_treasury_cover;
+
+/// Completed games are settled once the leaderboard is final,
+/// cancelled games right away (refunds are checked per account)
+fn is_settled(game_state: &GameState) -> bool {
+    match game_state.status {
+        GameStatus::Completed => game_state.leaderboard_finalized,
+        GameStatus::Cancelled => true,
+        GameStatus::Pending | GameStatus::Active => false,
+    }
+}
+
+fn require_settled(game_state: &GameState) -> Result<()> {
+    require!(is_settled(game_state), GameError::GameNotSettled);
+    Ok(())
+}
+
+/// Cancelled games need the refund taken, others the prize claimed
+fn player_state_settled(game_state: &GameState, player_state: &PlayerState) -> bool {
+    match game_state.status {
+        GameStatus::Cancelled => player_state.refund_claimed,
+        _ => !player_state.has_unclaimed_prize(game_state),
+    }
+}
+
+/// Only cancelled games refund sponsorships
+fn sponsorship_settled(game_state: &GameState, sponsorship: &Sponsorship) -> bool {
+    game_state.status != GameStatus::Cancelled || sponsorship.refunded
+}
+
+/// Every account the game opened is closed, each opened round left a
+/// RoundResult, cancelled games included
+fn game_accounts_closed(game_state: &GameState) -> bool {
+    game_state.closed_players == game_state.total_player
+        && game_state.closed_sponsors == game_state.sponsor_count
+        && game_state.closed_rounds == game_state.current_round
+}
+
+/// The game_id only frees up once every price snapshot of the game expired,
+/// a new game must not resolve on the old game's snapshots
+fn snapshots_expired(game_state: &GameState, now: i64) -> Result<bool> {
+    let retained_until = game_state
+        .end_time
+        .ok_or(GameError::GameNotSettled)?
+        .checked_add(SNAPSHOT_RETENTION_SECONDS)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    Ok(now >= retained_until)
+}
+
+/// Close a PlayerState once the player has nothing left to claim.
+/// Anyone can crank it, the rent always goes back to the player.
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ClosePlayerState<'info> {
+    pub caller: Signer<'info>,
+
+    /// GameState account
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// PlayerState account (closed)
+    #[account(
+        mut,
+        close = player,
+        seeds = [PLAYER_SEED, game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// Player who paid the rent at join_game
+    #[account(mut, address = player_state.player @ GameError::Unauthorized)]
+    pub player: SystemAccount<'info>,
+}
+
+pub fn close_player_state(ctx: Context<ClosePlayerState>, game_id: u64) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    let player_state = &ctx.accounts.player_state;
+
+    require_settled(game_state)?;
+    require!(
+        player_state_settled(game_state, player_state),
+        GameError::GameNotSettled
+    );
+
+    game_state.closed_players = game_state
+        .closed_players
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Player state closed!");
+    msg!("Game ID: {}", game_id);
+    msg!("Player: {}", player_state.player);
+
+    Ok(())
+}
+
+/// Close a RoundResult of a settled game, rent goes back to whoever opened the round
+#[derive(Accounts)]
+#[instruction(game_id: u64, round_number: u8)]
+pub struct CloseRoundResult<'info> {
+    pub caller: Signer<'info>,
+
+    /// GameState account
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// RoundResult account (closed)
+    #[account(
+        mut,
+        close = payer,
+        seeds = [ROUND_RESULT_SEED, game_id.to_le_bytes().as_ref(), &[round_number]],
+        bump = round_result.bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+
+    /// Caller of start_game / advance_round that paid the rent
+    #[account(mut, address = round_result.payer @ GameError::Unauthorized)]
+    pub payer: SystemAccount<'info>,
+}
+
+pub fn close_round_result(ctx: Context<CloseRoundResult>, game_id: u64, round_number: u8) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+
+    require_settled(game_state)?;
+
+    game_state.closed_rounds = game_state
+        .closed_rounds
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Round result closed!");
+    msg!("Game ID: {}", game_id);
+    msg!("Round: {}", round_number);
+
+    Ok(())
+}
+
+/// Close a Sponsorship record, cancelled games need the refund taken first
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CloseSponsorship<'info> {
+    pub caller: Signer<'info>,
+
+    /// GameState account
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Sponsorship account (closed)
+    #[account(
+        mut,
+        close = sponsor,
+        seeds = [SPONSOR_SEED, game_id.to_le_bytes().as_ref(), sponsor.key().as_ref()],
+        bump = sponsorship.bump,
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    /// Sponsor who paid the rent
+    #[account(mut, address = sponsorship.sponsor @ GameError::Unauthorized)]
+    pub sponsor: SystemAccount<'info>,
+}
+
+pub fn close_sponsorship(ctx: Context<CloseSponsorship>, game_id: u64) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+
+    require_settled(game_state)?;
+    require!(
+        sponsorship_settled(game_state, &ctx.accounts.sponsorship),
+        GameError::GameNotSettled
+    );
+
+    game_state.closed_sponsors = game_state
+        .closed_sponsors
+        .checked_add(1)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Sponsorship closed!");
+    msg!("Game ID: {}", game_id);
+    msg!("Sponsor: {}", ctx.accounts.sponsorship.sponsor);
+
+    Ok(())
+}
+
+/// Close a settled game once every player, round and sponsorship account is gone.
+/// A cancelled game's treasury cover goes back to the vault treasury, what is
+/// left in the pool after that (platform fee, rounding dust) goes to the platform
+/// admin, the pool token account and GameState rent go back to the creator.
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CloseGame<'info> {
+    pub caller: Signer<'info>,
+
+    /// GameState account (closed)
+    #[account(
+        mut,
+        close = creator,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Game creator that paid the rent
+    #[account(mut, address = game_state.creator @ GameError::Unauthorized)]
+    pub creator: SystemAccount<'info>,
+
+    /// GlobalVault PDA, source of the platform admin
+    #[account(
+        seeds = [GLOBAL_VAULT_SEED],
+        seeds::program = vault::ID,
+        bump = global_vault.bump,
+    )]
+    pub global_vault: Account<'info, GlobalVault>,
+
+    /// Mint the game is played in, receives withheld transfer fees
+    #[account(mut, address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Prize pool token account (closed)
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+        token::mint = token_mint,
+        token::authority = game_state,
+    )]
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Platform admin token account receiving what is left in the pool
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = global_vault.admin,
+    )]
+    pub platform_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Cancelled games with a treasury cover: vault treasury of the game's mint
+    #[account(mut)]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    /// Token account of the vault treasury
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub vault_program: Option<Program<'info, Vault>>,
+}
+
+pub fn close_game(ctx: Context<CloseGame>, game_id: u64) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require_settled(game_state)?;
+
+    require!(game_accounts_closed(game_state), GameError::AccountsStillOpen);
+    require!(
+        snapshots_expired(game_state, clock.unix_timestamp)?,
+        GameError::SnapshotsStillRetained
+    );
+
+    // Pool is owned by the game_state PDA
+    let game_id_bytes = game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    let returned_to_treasury = return_treasury_cover(
+        &ctx.accounts.game_state,
+        &mut ctx.accounts.prize_pool_token_account,
+        ctx.accounts.treasury.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        ctx.accounts.vault_program.as_ref(),
+    )?;
+
+    let swept_amount = ctx.accounts.prize_pool_token_account.amount;
+    if swept_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.prize_pool_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.platform_token_account.to_account_info(),
+                    authority: ctx.accounts.game_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            swept_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    harvest_withheld_fees(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.prize_pool_token_account,
+    )?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.prize_pool_token_account.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.game_state.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    msg!("Game closed!");
+    msg!("Game ID: {}", game_id);
+    msg!("Returned to treasury: {}", returned_to_treasury);
+    msg!("Swept to platform: {}", swept_amount);
+
+    emit!(GameClosed {
+        game_id,
+        closed_by: ctx.accounts.caller.key(),
+        swept_amount,
+        returned_to_treasury,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Transfer fee mints withhold part of every transfer in the receiving
+/// account, and close_account fails while any is left. Harvesting moves it
+/// to the mint, where the fee authority collects it, and needs no signer.
+fn harvest_withheld_fees<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    pool_account: &InterfaceAccount<'info, TokenAccount>,
+) -> Result<()> {
+    let mint_info = token_mint.to_account_info();
+    if *mint_info.owner != anchor_spl::token_2022::ID {
+        return Ok(());
+    }
+
+    let has_transfer_fee = {
+        let data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+        mint.get_extension::<TransferFeeConfig>().is_ok()
+    };
+    if !has_transfer_fee {
+        return Ok(());
+    }
+
+    harvest_withheld_tokens_to_mint(
+        CpiContext::new(
+            token_program.to_account_info(),
+            HarvestWithheldTokensToMint {
+                token_program_id: token_program.to_account_info(),
+                mint: mint_info,
+            },
+        ),
+        vec![pool_account.to_account_info()],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completed_game() -> GameState {
+        let mut game = GameState::test_game();
+        game.status = GameStatus::Completed;
+        game.leaderboard_finalized = true;
+        game.end_time = Some(50_000);
+        game
+    }
+
+    fn winner(prize_amount: u64) -> PlayerState {
+        let mut player = PlayerState::test_player(5);
+        player.ranked = true;
+        player.final_rank = Some(1);
+        player.prize_amount = prize_amount;
+        player
+    }
+
+    #[test]
+    fn only_finalized_or_cancelled_games_are_settled() {
+        let mut game = GameState::test_game();
+        assert!(!is_settled(&game));
+
+        game.status = GameStatus::Active;
+        assert!(!is_settled(&game));
+
+        game.status = GameStatus::Completed;
+        assert!(!is_settled(&game));
+        game.leaderboard_finalized = true;
+        assert!(is_settled(&game));
+
+        game.status = GameStatus::Cancelled;
+        game.leaderboard_finalized = false;
+        assert!(is_settled(&game));
+    }
+
+    #[test]
+    fn winners_must_claim_before_their_state_closes() {
+        let game = completed_game();
+        let mut player = winner(1_000);
+        assert!(!player_state_settled(&game, &player));
+
+        player.prize_claimed = true;
+        assert!(player_state_settled(&game, &player));
+
+        assert!(player_state_settled(&game, &winner(0)));
+        assert!(player_state_settled(&game, &PlayerState::test_player(5)));
+    }
+
+    #[test]
+    fn survivors_without_a_prize_can_close() {
+        let mut game = completed_game();
+        game.game_mode = GameMode::Survivor;
+        game.winning_round = u8::MAX;
+
+        let mut survivor = PlayerState::test_player(5);
+        survivor.ranked = true;
+        assert!(player_state_settled(&game, &survivor));
+
+        survivor.prize_amount = 1_000;
+        assert!(!player_state_settled(&game, &survivor));
+
+        // eliminated before the winning round
+        survivor.eliminated_round = Some(3);
+        assert!(player_state_settled(&game, &survivor));
+    }
+
+    #[test]
+    fn cancelled_players_must_take_their_refund() {
+        let mut game = GameState::test_game();
+        game.status = GameStatus::Cancelled;
+        let mut player = PlayerState::test_player(5);
+        assert!(!player_state_settled(&game, &player));
+
+        player.refund_claimed = true;
+        assert!(player_state_settled(&game, &player));
+    }
+
+    #[test]
+    fn sponsorships_wait_for_refunds_only_when_cancelled() {
+        let mut sponsorship = Sponsorship {
+            game_id: 1,
+            sponsor: Pubkey::new_unique(),
+            amount: 500,
+            refunded: false,
+            bump: 255,
+        };
+        assert!(sponsorship_settled(&completed_game(), &sponsorship));
+
+        let mut game = GameState::test_game();
+        game.status = GameStatus::Cancelled;
+        assert!(!sponsorship_settled(&game, &sponsorship));
+        sponsorship.refunded = true;
+        assert!(sponsorship_settled(&game, &sponsorship));
+    }
+
+    #[test]
+    fn game_closes_once_every_account_is_closed() {
+        let mut game = completed_game();
+        game.total_player = 3;
+        game.sponsor_count = 1;
+        game.current_round = 5;
+        assert!(!game_accounts_closed(&game));
+
+        game.closed_players = 3;
+        game.closed_sponsors = 1;
+        game.closed_rounds = 4;
+        assert!(!game_accounts_closed(&game));
+
+        game.closed_rounds = 5;
+        assert!(game_accounts_closed(&game));
+    }
+
+    #[test]
+    fn game_id_stays_taken_while_snapshots_are_retained() {
+        let game = completed_game();
+        let expires = 50_000 + SNAPSHOT_RETENTION_SECONDS;
+        assert!(!snapshots_expired(&game, expires - 1).unwrap());
+        assert!(snapshots_expired(&game, expires).unwrap());
+
+        assert!(snapshots_expired(&GameState::test_game(), expires).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::PredictionCommitted;
+use crate::instructions::submit_prediction::record_prediction;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, round_number: u8)]
+pub struct CommitPrediction<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_SEED, game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        constraint = player_state.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    #[account(
+        seeds = [ROUND_RESULT_SEED, game_id.to_le_bytes().as_ref(), &[round_number]],
+        bump = round_result.bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+}
+
+pub fn handler(
+    ctx: Context<CommitPrediction>,
+    game_id: u64,
+    round_number: u8,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let player_state = &mut ctx.accounts.player_state;
+    let round_result = &ctx.accounts.round_result;
+    let clock = Clock::get()?;
+
+    require!(game_state.commit_reveal, GameError::CommitRevealDisabled);
+
+    // response time counts from the commit, the reveal only proves the choice
+    let response_time = record_prediction(
+        game_state,
+        player_state,
+        round_result,
+        round_number,
+        None,
+        Some(commitment),
+        clock.unix_timestamp,
+    )?;
+
+    msg!("Prediction committed!");
+    msg!("Player: {}", ctx.accounts.player.key());
+    msg!("Round: {}", round_number);
+    msg!("Response time: {}ms", response_time * 1000);
+
+    emit!(PredictionCommitted {
+        game_id,
+        player: ctx.accounts.player.key(),
+        round_number,
+        commitment,
+        response_time,
+        submitted_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::crank::pay_crank_reward;
+use crate::errors::GameError;
+use crate::events::GameCompleted;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CompleteGame<'info> {
+    /// Creator, or anyone once the grace period after the last round passed
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// RoundResult of the final round, must be resolved
+    #[account(
+        seeds = [ROUND_RESULT_SEED, game_id.to_le_bytes().as_ref(), &[game_state.total_round]],
+        bump = last_round_result.bump,
+    )]
+    pub last_round_result: Account<'info, RoundResult>,
+
+    /// Prize pool token account (only needed to pay the crank reward)
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+    )]
+    pub prize_pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Caller's token account receiving the crank reward
+    #[account(mut)]
+    pub caller_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint the game is played in (needed for token transfers)
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn handler(ctx: Context<CompleteGame>, _game_id: u64) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require!(
+        game_state.status == GameStatus::Active,
+        GameError::InvalidGameStatus
+    );
+
+    // last round must have been opened
+    require!(
+        game_state.current_round == game_state.total_round,
+        GameError::InvalidRoundNumber
+    );
+
+    // last round must be resolved before the game leaves Active
+    require!(
+        ctx.accounts.last_round_result.correct_answer.is_some(),
+        GameError::RoundNotResolved
+    );
+
+    // non-creator cranks wait for the grace period after the last deadline
+    if game_state.creator != ctx.accounts.caller.key() {
+        let last_deadline = game_state.round_deadline[(game_state.total_round - 1) as usize];
+        let complete_after = last_deadline
+            .checked_add(COMPLETE_GAME_GRACE_SECONDS)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        require!(
+            clock.unix_timestamp >= complete_after,
+            GameError::DeadlineNotReached
+        );
+    }
+
+    // leaderboard is finalized afterwards by finalize_leaderboard
+    game_state.status = GameStatus::Completed;
+    game_state.end_time = Some(clock.unix_timestamp);
+
+    pay_crank_reward(
+        game_state,
+        &ctx.accounts.caller,
+        ctx.accounts.prize_pool_token_account.as_ref(),
+        ctx.accounts.caller_token_account.as_ref(),
+        ctx.accounts.token_mint.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    msg!("Game completed!");
+    msg!("Final players: {}", game_state.total_player);
+    msg!("Prize pool: {}", game_state.prize_pool);
+    msg!("Top scorer: {:?}", game_state.top_scorer);
+    msg!("Highest score: {}", game_state.higest_score);
+
+    emit!(GameCompleted {
+        game_id: game_state.game_id,
+        completed_by: ctx.accounts.caller.key(),
+        total_player: game_state.total_player,
+        prize_pool: game_state.prize_pool,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
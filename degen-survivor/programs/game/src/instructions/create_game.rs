@@ -1,29 +1,52 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use vault::constants::GLOBAL_VAULT_SEED;
+use vault::state::GlobalVault;
 
 use crate::constants::*;
 use crate::errors::GameError;
+use crate::events::GameCreated;
 use crate::state::*;
 
 #[derive(Accounts)]
-#[instruction(game_id: u64)]
+#[instruction(game_id: u64, game_type: GameType, start_time: i64, entry_fee: u64, crank_reward: u64, options: GameOptions)]
 pub struct CreateGame<'info> {
     /// Backend authority creating the game
     #[account(mut)]
     pub creator: Signer<'info>,
 
-    /// GameState account
+    /// Template the game rounds, timing and limits are copied from
+    #[account(
+        seeds = [TEMPLATE_SEED, template.template_id.to_le_bytes().as_ref()],
+        bump = template.bump,
+        constraint = template.active @ GameError::TemplateInactive,
+    )]
+    pub template: Account<'info, GameTemplate>,
+
+    /// GameState account, sized for the template round count
     #[account(
         init,
         payer = creator,
-        space = GameState::SIZE,
+        space = GameState::space(template.config.total_rounds),
         seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
         bump
     )]
     pub game_state: Account<'info, GameState>,
 
-    /// DEGEN token mint
-    pub token_mint: Account<'info, Mint>,
+    /// GlobalVault PDA, its DEGEN mint is the default game mint
+    #[account(
+        seeds = [GLOBAL_VAULT_SEED],
+        seeds::program = vault::ID,
+        bump = global_vault.bump,
+    )]
+    pub global_vault: Account<'info, GlobalVault>,
+
+    /// Mint the game is played in (options.token_mint or the DEGEN mint)
+    #[account(
+        address = options.token_mint.unwrap_or(global_vault.token_mint) @ GameError::InvalidTokenMint,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// Prize pool token account (holds all entry fees)
     #[account(
@@ -31,11 +54,12 @@ pub struct CreateGame<'info> {
         payer = creator,
         token::mint = token_mint,
         token::authority = game_state,
+        token::token_program = token_program,
     )]
-    pub prize_pool_token_account: Account<'info, TokenAccount>,
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Token program
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// System program
     pub system_program: Program<'info, System>,
@@ -47,7 +71,21 @@ pub fn handler(
     game_type: GameType,
     start_time: i64,
     entry_fee: u64,
+    crank_reward: u64,
+    options: GameOptions,
 ) -> Result<()> {
+    let GameOptions {
+        commit_reveal,
+        game_mode,
+        elimination_threshold,
+        lives,
+        speed_bonus,
+        streak_multipliers,
+        allowlist_root,
+        access_key,
+        token_mint: _,
+    } = options;
+    let config = ctx.accounts.template.config.clone();
     let game_state = &mut ctx.accounts.game_state;
     let clock = Clock::get()?;
     
@@ -56,6 +94,54 @@ pub fn handler(
         start_time > clock.unix_timestamp,
         GameError::InvalidStartTime
     );
+
+    // Crank reward must stay a small slice of the entry fee
+    let max_crank_reward = (entry_fee as u128)
+        .checked_mul(MAX_CRANK_REWARD_BPS as u128)
+        .and_then(|v| v.checked_div(BPS_DIVISOR as u128))
+        .ok_or(GameError::ArithmeticOverflow)?;
+    require!(
+        (crank_reward as u128) <= max_crank_reward,
+        GameError::CrankRewardTooHigh
+    );
+
+
+    // every round of the template must be playable in this game type
+    require!(
+        config.round_types.iter().all(|round_type| round_type.supported_by(game_type)),
+        GameError::UnsupportedRoundType
+    );
+
+    // Survivor needs a threshold a wrong answer falls under and at least
+    // one life, Classic neither
+    let survivor_rules_valid = match game_mode {
+        GameMode::Classic => elimination_threshold == 0 && lives == 0,
+        GameMode::Survivor => {
+            elimination_threshold > POINT_WRONG
+                && elimination_threshold <= POINT_EXCATE
+                && lives >= 1
+        }
+    };
+    require!(survivor_rules_valid, GameError::InvalidEliminationThreshold);
+
+    if let Some(bonus) = speed_bonus {
+        let decay_valid = match bonus.decay {
+            BonusDecay::Linear | BonusDecay::Quadratic => true,
+            BonusDecay::HalfLife { seconds } => seconds > 0,
+        };
+        require!(
+            bonus.max_bonus_bps > 0 && bonus.max_bonus_bps <= MAX_SPEED_BONUS_BPS && decay_valid,
+            GameError::InvalidSpeedBonus
+        );
+    }
+
+    // multipliers never shrink as the streak grows
+    if let Some(table) = streak_multipliers {
+        let in_range = table.iter()
+            .all(|&bps| bps as u64 >= BPS_DIVISOR && bps <= MAX_STREAK_MULTIPLIER_BPS);
+        let non_decreasing = table.windows(2).all(|pair| pair[0] <= pair[1]);
+        require!(in_range && non_decreasing, GameError::InvalidStreakMultipliers);
+    }
     
     // Initialize game state
     game_state.game_id = game_id;
@@ -67,45 +153,103 @@ pub fn handler(
     game_state.actual_start_time = None;
     game_state.end_time = None;
     game_state.current_round = 0;
-    game_state.total_round = TOTAL_ROUNDS;
+    game_state.total_round = config.total_rounds;
     
-    // Calculate round deadlines (start + 0min, +2min, +4min, +6min, +8min)
-    game_state.round_deadline = [
-        start_time + ROUND_DURATION_SECONDS,
-        start_time + (ROUND_GAP_SECONDS * 1) + ROUND_DURATION_SECONDS,
-        start_time + (ROUND_GAP_SECONDS * 2) + ROUND_DURATION_SECONDS,
-        start_time + (ROUND_GAP_SECONDS * 3) + ROUND_DURATION_SECONDS,
-        start_time + (ROUND_GAP_SECONDS * 4) + ROUND_DURATION_SECONDS,
-    ];
+    // Calculate round deadlines (round i ends at start + i * gap + duration)
+    let round_deadline = (0..config.total_rounds as i64)
+        .map(|i| {
+            config
+                .round_gap_seconds
+                .checked_mul(i)
+                .and_then(|v| v.checked_add(start_time))
+                .and_then(|v| v.checked_add(config.round_duration_seconds))
+                .ok_or(GameError::ArithmeticOverflow)
+        })
+        .collect::<std::result::Result<Vec<i64>, GameError>>()?;
+    game_state.round_deadline = round_deadline;
     
     game_state.entry_fee = entry_fee;
     game_state.prize_pool = 0;
     game_state.prize_pool_token_account = ctx.accounts.prize_pool_token_account.key();
-    game_state.platform_fee_bps = PLATFORM_FEE_BPS;
+    game_state.platform_fee_bps = config.platform_fee_bps;
     game_state.prize_pool_distributed = false;
     game_state.total_player = 0;
-    game_state.max_player = MAX_PLAYER;
+    game_state.max_player = config.max_player;
+    game_state.min_player = config.min_player;
     game_state.player_finalized = false;
     
-    // Define round types for this game
-    game_state.round_types = [
-        RoundType::PriceDirection,
-        RoundType::Magnitude,
-        RoundType::Comperative,
-        RoundType::Range,
-        RoundType::Trend,
-    ];
+    // Round types and timing for this game
+    game_state.round_types = config.round_types;
+    game_state.round_duration_seconds = config.round_duration_seconds;
+    game_state.round_gap_seconds = config.round_gap_seconds;
+    game_state.prediction_lockout_seconds = config.prediction_lockout_seconds;
+    game_state.range_zone_width_bps = config.range_zone_width_bps;
+    game_state.btc_magnitude_buckets = config.btc_magnitude_buckets;
+    game_state.sol_magnitude_buckets = config.sol_magnitude_buckets;
+    game_state.template = ctx.accounts.template.key();
+    game_state.commit_reveal = commit_reveal;
+    game_state.game_mode = game_mode;
+    game_state.elimination_threshold = elimination_threshold;
+    game_state.starting_lives = lives;
+    game_state.speed_bonus = speed_bonus;
+    game_state.streak_multipliers = streak_multipliers;
+    game_state.allowlist_root = allowlist_root;
+    game_state.access_key = access_key;
+    game_state.token_mint = ctx.accounts.token_mint.key();
+    game_state.guaranteed_prize = 0;
+    game_state.sponsored_amount = 0;
+    game_state.treasury_cover = 0;
+    game_state.sponsor_count = 0;
+    game_state.closed_players = 0;
+    game_state.closed_sponsors = 0;
+    game_state.closed_rounds = 0;
+    game_state.crank_paid = 0;
+    game_state.refund_bps = 0;
+    game_state.join_count = 0;
+    game_state.winning_round = 0;
+    game_state.winner_count = 0;
     
     game_state.leaderboard_finalized = false;
     game_state.top_scorer = None;
     game_state.higest_score = 0;
+    game_state.ranked_players = 0;
+    game_state.completed_players = 0;
+    game_state.leaderboard = Vec::new();
     game_state.bump = ctx.bumps.game_state;
+    game_state.crank_reward = crank_reward;
     
     msg!("Game created!");
     msg!("Game ID: {}", game_id);
     msg!("Type: {:?}", game_type);
     msg!("Start time: {}", start_time);
     msg!("Entry fee: {}", entry_fee);
+    msg!("Crank reward: {}", crank_reward);
+    msg!("Template: {}", ctx.accounts.template.template_id);
+    msg!("Commit-reveal: {}", commit_reveal);
+    msg!("Mode: {:?}", game_mode);
+    msg!("Lives: {}", lives);
+    msg!("Speed bonus: {:?}", speed_bonus);
+    msg!("Streak multipliers: {:?}", streak_multipliers);
+    msg!("Private: {}", allowlist_root.is_some() || access_key.is_some());
+
+    emit!(GameCreated {
+        game_id,
+        creator: game_state.creator,
+        template: game_state.template,
+        game_type,
+        start_time,
+        entry_fee,
+        token_mint: game_state.token_mint,
+        total_rounds: game_state.total_round,
+        max_player: game_state.max_player,
+        crank_reward,
+        commit_reveal,
+        game_mode,
+        lives,
+        speed_bonus,
+        streak_multipliers,
+        private: allowlist_root.is_some() || access_key.is_some(),
+    });
     
     Ok(())
 }
\ No newline at end of file
@@ -2,18 +2,19 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::GameError;
+use crate::events::{PlayerEliminated, PlayerEvaluated};
 use crate::state::*;
 
 #[derive(Accounts)]
 #[instruction(game_id: u64, round_number: u8)]
 
 pub struct EvualatedRound <'info> {
-    #[account(mut)]
-    pub creator : Signer<'info>,
+    /// Anyone can evaluate, same as evaluate_round_batch
+    pub caller : Signer<'info>,
     #[account(
+        mut,
         seeds = [GAME_SEED , game_id.to_le_bytes().as_ref()],
         bump = game_state.bump,
-        constraint = game_state.creator == creator.key() @ GameError::Unauthorized,
     )]
     pub game_state   : Account<'info,GameState>,
 
@@ -36,65 +37,274 @@ pub fn handler(ctx:Context<EvualatedRound>,_gmae_id : u64,round_number:u8 )->Res
     let round_result = &mut ctx.accounts.round_result;
     let clock = Clock::get()?;
 
+    require_round_evaluable(&ctx.accounts.game_state, round_result, round_number, clock.unix_timestamp)?;
+
+    let mut tally = RoundTally::default();
+    let outcome = evaluate_player(player_state, round_result, round_number, &mut ctx.accounts.game_state)?;
+    tally.record(&outcome);
+    tally.apply(round_result)?;
+
+    Ok(())
+}
+
+/// Round must be over and resolved before anyone is scored.
+/// Commit-reveal games also wait for the reveal window to close.
+pub fn require_round_evaluable(game_state: &GameState, round_result: &RoundResult, round_number: u8, now: i64) -> Result<()> {
+    require!(round_result.round_number == round_number, GameError::InvalidRoundNumber);
+
     // check round is ended 
-    require!(clock.unix_timestamp> round_result.round_end_ts,GameError::RoundNotEnded);
-
-    // check for predection 
-    let predection = player_state.get_prediction(round_number).ok_or(GameError::NoPredictionFound)?;
-    
-     // Check not already evaluated
-     require!(
-        predection.point_earned == 0,
+    require!(now > round_result.round_end_ts, GameError::RoundNotEnded);
+
+    if game_state.commit_reveal {
+        let reveal_end = round_result.round_end_ts
+            .checked_add(REVEAL_WINDOW_SECONDS)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        require!(now > reveal_end, GameError::RoundNotEnded);
+    }
+
+    require!(round_result.correct_answer.is_some(), GameError::RoundNotResolved);
+    Ok(())
+}
+
+/// Score one player for one round and update their totals.
+/// Rounds are evaluated strictly in order, a missed prediction scores zero
+/// and an unrevealed commitment is scored as wrong. In Survivor games a
+/// round under the elimination threshold costs a life and the player is
+/// knocked out at zero, later rounds of an eliminated player score zero.
+/// The optional speed bonus is added to the score and the streak multiplier
+/// scales it, correctness, tallies and elimination use the base points.
+/// Streaks rely on the strict round order enforced by round_evaluated.
+/// After the last round the player is entered on the game leaderboard.
+pub fn evaluate_player(player_state: &mut PlayerState, round_result: &RoundResult, round_number: u8, game_state: &mut GameState) -> Result<RoundOutcome> {
+    // Check not already evaluated and no round skipped
+    require!(
+        player_state.round_evaluated < round_number,
         GameError::AlreadyEvaluated
     );
-
-    let correct_answer = round_result.correct_answer.ok_or(GameError::NoPredictionFound)?;
-    let points = calculate_points(
-        round_result.round_type,
-        predection.choice,
-        correct_answer,
-        round_result.price_change_btc,
-        round_result.price_change_sol,
+    require!(
+        player_state.round_evaluated == round_number - 1,
+        GameError::InvalidRoundNumber
     );
 
-    // update player predection per round 
+    let correct_answer = round_result.correct_answer.ok_or(GameError::RoundNotResolved)?;
+    let index = (round_number - 1) as usize;
+    let total_rounds = game_state.total_round;
+    let already_eliminated = player_state.is_eliminated();
+
+    let (points, bonus) = match &mut player_state.predection[index] {
+        Some(pred) => {
+            let points = match pred.choice {
+                Some(_) if already_eliminated => POINT_WRONG,
+                Some(choice) => calculate_points(
+                    round_result.round_type,
+                    choice,
+                    correct_answer,
+                    round_result.price_change_btc,
+                    round_result.price_change_sol,
+                ),
+                None => POINT_WRONG,
+            };
+
+            let bonus = match game_state.speed_bonus {
+                Some(speed_bonus) => calculate_speed_bonus(
+                    &speed_bonus,
+                    points,
+                    pred.response_time,
+                    prediction_window(round_result, game_state),
+                ),
+                None => 0,
+            };
+
+            // point_earned is set once the streak is applied
+            pred.is_correct = points == POINT_EXCATE;
+            (Some(points), bonus)
+        }
+        None => {
+            // a missed round counts as the slowest possible answer
+            let round_duration = round_result.round_end_ts
+                .checked_sub(round_result.round_start_ts)
+                .ok_or(GameError::ArithmeticOverflow)?;
+            player_state.total_reponse_time = player_state.total_reponse_time
+                .checked_add(round_duration as u64)
+                .ok_or(GameError::ArithmeticOverflow)?;
+            (None, 0)
+        }
+    };
 
-    if let Some(pred) = &mut player_state.predection[(round_number - 1) as usize] {
-        pred.point_earned = points;
-        pred.is_correct = points == POINT_EXCATE;
-    }
-    
      // Update player scores
-     player_state.scores[(round_number - 1) as usize] = points;
+     let earned = points.unwrap_or(POINT_WRONG);
+
+     let lost_life = game_state.game_mode == GameMode::Survivor
+         && !already_eliminated
+         && earned < game_state.elimination_threshold;
+     if lost_life {
+         player_state.lives_remaining = player_state.lives_remaining.saturating_sub(1);
+     }
+
+     let knocked_out = lost_life && player_state.lives_remaining == 0;
+     if knocked_out {
+         player_state.eliminated_round = Some(round_number);
+         emit!(PlayerEliminated {
+             game_id: player_state.game_id,
+             player: player_state.player,
+             round_number,
+         });
+     }
+
+     if earned == POINT_EXCATE && !already_eliminated {
+         player_state.streak = player_state.streak.saturating_add(1);
+         player_state.best_streak = player_state.best_streak.max(player_state.streak);
+     } else {
+         player_state.streak = 0;
+     }
+
+     let scored = apply_streak_multiplier(
+         game_state.streak_multipliers.as_ref(),
+         player_state.streak,
+         earned.checked_add(bonus).ok_or(GameError::ArithmeticOverflow)?,
+     )?;
+     // update player predection per round 
+     if let Some(pred) = player_state.predection[index].as_mut() {
+         pred.point_earned = scored;
+     }
+     player_state.scores[index] = scored;
      player_state.total_score = player_state.total_score
-         .checked_add(points)
+         .checked_add(scored)
          .ok_or(GameError::ArithmeticOverflow)?;
      
      player_state.round_evaluated += 1;
 
-     if player_state.round_evaluated == TOTAL_ROUNDS {
+     if player_state.round_evaluated == total_rounds {
         player_state.all_round_completed = true;
         
         // Calculate average response time
         player_state.avg_response_time = 
-            (player_state.total_reponse_time / TOTAL_ROUNDS as u64) as u32;
+            (player_state.total_reponse_time / total_rounds as u64) as u32;
+
+        game_state.record_completed(player_state.player, RankKey::from_player(player_state))?;
     }
 
-    round_result.total_predection += 1;
+    emit!(PlayerEvaluated {
+        game_id: player_state.game_id,
+        player: player_state.player,
+        round_number,
+        points,
+        speed_bonus: bonus,
+        streak: player_state.streak,
+        total_score: player_state.total_score,
+        lives_remaining: player_state.lives_remaining,
+        eliminated: player_state.is_eliminated(),
+    });
 
-     
-    if points == POINT_EXCATE {
-        round_result.correct_predection += 1;
-    } else if points == POINT_PARTIAL || points == POINT_CLOSER {
-        round_result.partial_correct += 1;
-    } else {
-        round_result.wrong_predection += 1;
+    Ok(RoundOutcome { points, knocked_out })
+}
+
+/// Seconds of a round in which predictions are accepted
+fn prediction_window(round_result: &RoundResult, game_state: &GameState) -> i64 {
+    round_result.round_end_ts
+        .saturating_sub(round_result.round_start_ts)
+        .saturating_sub(game_state.prediction_lockout_seconds)
+}
+
+/// Bonus points for answering `response_time` seconds into the window
+pub fn calculate_speed_bonus(speed_bonus: &SpeedBonus, points: u16, response_time: u32, window: i64) -> u16 {
+    if points == POINT_WRONG || window <= 0 {
+        return 0;
     }
-    Ok(())
+
+    let window = window as u128;
+    let elapsed = (response_time as u128).min(window);
+    let remaining = window - elapsed;
+    let max_bps = speed_bonus.max_bonus_bps as u128;
+
+    let bonus_bps = match speed_bonus.decay {
+        BonusDecay::Linear => max_bps * remaining / window,
+        BonusDecay::Quadratic => max_bps * remaining * remaining / (window * window),
+        BonusDecay::HalfLife { seconds } => {
+            let halvings = elapsed / seconds.max(1) as u128;
+            if halvings >= 16 { 0 } else { max_bps >> halvings }
+        }
+    };
+
+    (points as u128 * bonus_bps / BPS_DIVISOR as u128) as u16
 }
 
+/// Scale points by the multiplier for the current streak, a streak longer
+/// than the table uses its last entry
+pub fn apply_streak_multiplier(table: Option<&[u16; STREAK_TIERS]>, streak: u8, points: u16) -> Result<u16> {
+    let Some(table) = table else { return Ok(points) };
+    if streak == 0 {
+        return Ok(points);
+    }
+
+    let tier = (streak as usize).min(STREAK_TIERS) - 1;
+    let scaled = (points as u64)
+        .checked_mul(table[tier] as u64)
+        .ok_or(GameError::ArithmeticOverflow)?
+        / BPS_DIVISOR;
 
-fn calculate_points (round_type: RoundType,
+    u16::try_from(scaled).map_err(|_| GameError::ArithmeticOverflow.into())
+}
+
+/// Result of scoring one player for one round
+pub struct RoundOutcome {
+    /// Points earned, None if the player did not predict
+    pub points: Option<u16>,
+    /// Survivor: the player was eliminated by this round
+    pub knocked_out: bool,
+}
+
+/// Per-round prediction counters, accumulated then written once
+#[derive(Default)]
+pub struct RoundTally {
+    pub total: u16,
+    pub correct: u16,
+    pub partial: u16,
+    pub wrong: u16,
+    pub knocked_out: u16,
+}
+
+impl RoundTally {
+    pub fn record(&mut self, outcome: &RoundOutcome) {
+        if outcome.knocked_out {
+            self.knocked_out += 1;
+        }
+
+        // players without a prediction are not counted
+        let Some(points) = outcome.points else { return };
+
+        self.total += 1;
+        if points == POINT_EXCATE {
+            self.correct += 1;
+        } else if points == POINT_PARTIAL || points == POINT_CLOSER {
+            self.partial += 1;
+        } else {
+            self.wrong += 1;
+        }
+    }
+
+    pub fn apply(&self, round_result: &mut RoundResult) -> Result<()> {
+        round_result.total_predection = round_result.total_predection
+            .checked_add(self.total)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        round_result.correct_predection = round_result.correct_predection
+            .checked_add(self.correct)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        round_result.partial_correct = round_result.partial_correct
+            .checked_add(self.partial)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        round_result.wrong_predection = round_result.wrong_predection
+            .checked_add(self.wrong)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        round_result.knocked_out = round_result.knocked_out
+            .checked_add(self.knocked_out)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+
+pub fn calculate_points (round_type: RoundType,
         player_choice : PredectionChoice,
         correct_answer : PredectionChoice,
         _price_chage_btc :i64,
@@ -190,4 +400,86 @@ fn calculate_points (round_type: RoundType,
             2 => POINT_FAR,    // 2 zones away
             _ => POINT_WRONG,  // 3+ zones away
         }
-    }
\ No newline at end of file
+    }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bonus(max_bonus_bps: u16, decay: BonusDecay) -> SpeedBonus {
+        SpeedBonus { max_bonus_bps, decay }
+    }
+
+    #[test]
+    fn linear_bonus_shrinks_to_zero_at_lockout() {
+        let linear = bonus(5_000, BonusDecay::Linear);
+        assert_eq!(calculate_speed_bonus(&linear, POINT_EXCATE, 0, 100), 50);
+        assert_eq!(calculate_speed_bonus(&linear, POINT_EXCATE, 50, 100), 25);
+        assert_eq!(calculate_speed_bonus(&linear, POINT_EXCATE, 100, 100), 0);
+        // answers timed after the window are clamped to it
+        assert_eq!(calculate_speed_bonus(&linear, POINT_EXCATE, 500, 100), 0);
+    }
+
+    #[test]
+    fn quadratic_bonus_drops_faster_than_linear() {
+        let quadratic = bonus(5_000, BonusDecay::Quadratic);
+        assert_eq!(calculate_speed_bonus(&quadratic, POINT_EXCATE, 0, 100), 50);
+        assert_eq!(calculate_speed_bonus(&quadratic, POINT_EXCATE, 50, 100), 12);
+        assert_eq!(calculate_speed_bonus(&quadratic, POINT_EXCATE, 100, 100), 0);
+    }
+
+    #[test]
+    fn half_life_bonus_halves_per_period() {
+        let half_life = bonus(8_000, BonusDecay::HalfLife { seconds: 10 });
+        assert_eq!(calculate_speed_bonus(&half_life, POINT_EXCATE, 9, 300), 80);
+        assert_eq!(calculate_speed_bonus(&half_life, POINT_EXCATE, 10, 300), 40);
+        assert_eq!(calculate_speed_bonus(&half_life, POINT_EXCATE, 25, 300), 20);
+        assert_eq!(calculate_speed_bonus(&half_life, POINT_EXCATE, 160, 300), 0);
+
+        // a zero period is treated as one second
+        let instant = bonus(8_000, BonusDecay::HalfLife { seconds: 0 });
+        assert_eq!(calculate_speed_bonus(&instant, POINT_EXCATE, 1, 300), 40);
+    }
+
+    #[test]
+    fn no_bonus_for_wrong_answers_or_empty_windows() {
+        let linear = bonus(5_000, BonusDecay::Linear);
+        assert_eq!(calculate_speed_bonus(&linear, POINT_WRONG, 0, 100), 0);
+        assert_eq!(calculate_speed_bonus(&linear, POINT_EXCATE, 0, 0), 0);
+        assert_eq!(calculate_speed_bonus(&linear, POINT_EXCATE, 0, -5), 0);
+    }
+
+    #[test]
+    fn partial_answers_earn_a_proportional_bonus() {
+        let linear = bonus(5_000, BonusDecay::Linear);
+        assert_eq!(calculate_speed_bonus(&linear, POINT_PARTIAL, 0, 100), 25);
+    }
+
+    const STREAKS: [u16; STREAK_TIERS] = [10_000, 12_500, 15_000, 20_000];
+
+    #[test]
+    fn streak_multiplier_follows_the_tier_table() {
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), 1, 100).unwrap(), 100);
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), 2, 100).unwrap(), 125);
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), 3, 100).unwrap(), 150);
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), 4, 100).unwrap(), 200);
+    }
+
+    #[test]
+    fn long_streaks_use_the_last_tier() {
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), 9, 100).unwrap(), 200);
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), u8::MAX, 100).unwrap(), 200);
+    }
+
+    #[test]
+    fn no_table_or_no_streak_keeps_points() {
+        assert_eq!(apply_streak_multiplier(None, 3, 100).unwrap(), 100);
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), 0, 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn streak_multiplier_rounds_down_and_rejects_overflow() {
+        assert_eq!(apply_streak_multiplier(Some(&STREAKS), 2, 1).unwrap(), 1);
+        assert!(apply_streak_multiplier(Some(&STREAKS), 4, u16::MAX).is_err());
+    }
+}
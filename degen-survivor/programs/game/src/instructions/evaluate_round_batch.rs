@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::instructions::evaluate_round::{evaluate_player, require_round_evaluable, RoundTally};
+use crate::state::*;
+
+/// PlayerState accounts to score are passed (writable) in remaining_accounts
+#[derive(Accounts)]
+#[instruction(game_id: u64, round_number: u8)]
+pub struct EvaluateRoundBatch<'info> {
+    /// Anyone can evaluate, scoring only depends on the resolved round
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_RESULT_SEED, game_id.to_le_bytes().as_ref(), &[round_number]],
+        bump = round_result.bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, EvaluateRoundBatch<'info>>,
+    game_id: u64,
+    round_number: u8,
+) -> Result<()> {
+    let round_result = &mut ctx.accounts.round_result;
+    let clock = Clock::get()?;
+
+    require_round_evaluable(&ctx.accounts.game_state, round_result, round_number, clock.unix_timestamp)?;
+
+    let mut tally = RoundTally::default();
+
+    for account_info in ctx.remaining_accounts.iter() {
+        let mut player_state = load_player_state(account_info, game_id, ctx.program_id)?;
+
+        let outcome = evaluate_player(&mut player_state, round_result, round_number, &mut ctx.accounts.game_state)?;
+        tally.record(&outcome);
+
+        player_state.exit(ctx.program_id)?;
+    }
+
+    tally.apply(round_result)?;
+
+    msg!("Round batch evaluated!");
+    msg!("Game ID: {}", game_id);
+    msg!("Round: {}", round_number);
+    msg!("Players evaluated: {}", ctx.remaining_accounts.len());
+    msg!("Correct: {} Partial: {} Wrong: {}", tally.correct, tally.partial, tally.wrong);
+    msg!("Knocked out: {}", tally.knocked_out);
+
+    Ok(())
+}
+
+/// Load a writable PlayerState passed through remaining_accounts and check
+/// it is the PLAYER_SEED PDA of this game
+pub fn load_player_state<'info>(
+    account_info: &'info AccountInfo<'info>,
+    game_id: u64,
+    program_id: &Pubkey,
+) -> Result<Account<'info, PlayerState>> {
+    require!(account_info.is_writable, GameError::InvalidPlayerAccount);
+
+    // owner + discriminator are checked on deserialize
+    let player_state: Account<'info, PlayerState> = Account::try_from(account_info)?;
+
+    let expected = Pubkey::create_program_address(
+        &[
+            PLAYER_SEED,
+            game_id.to_le_bytes().as_ref(),
+            player_state.player.as_ref(),
+            &[player_state.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| GameError::InvalidPlayerAccount)?;
+    require_keys_eq!(expected, account_info.key(), GameError::InvalidPlayerAccount);
+
+    Ok(player_state)
+}
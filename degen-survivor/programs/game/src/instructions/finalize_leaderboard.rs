@@ -2,81 +2,163 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::GameError;
+use crate::events::{LeaderboardFinalized, PlayerRanked};
+use crate::instructions::evaluate_round_batch::load_player_state;
 use crate::state::*;
 
+/// PlayerState accounts are passed (writable) in remaining_accounts, in any order,
+/// each followed by the player's (writable) PlayerProfile
 #[derive(Accounts)]
 #[instruction(game_id: u64)]
 
 pub struct FinalizedLeaderboard<'info>{
-    #[account(mut)]
-    pub creator : Signer<'info>,
+    /// Anyone can finalize, ranks come from the leaderboard kept on GameState
+    pub caller : Signer<'info>,
     
     #[account(
         mut,
         seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
         bump = game_state.bump,
-        constraint = game_state.creator == creator.key() @ GameError::Unauthorized,
     )]
     pub game_state: Account<'info, GameState>,
-
-    /// PlayerState account to set rank (passed in by backend)
-    #[account(
-        mut,
-        seeds = [PLAYER_SEED, game_id.to_le_bytes().as_ref(), player_state.player.key().as_ref()],
-        bump = player_state.bump,
-    )]
-    pub player_state: Account<'info, PlayerState>,
 }
 
-pub fn handler(ctx:Context<FinalizedLeaderboard>, _game_id:u64,rank:u16)->Result<()>{
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizedLeaderboard<'info>>,
+    game_id: u64,
+) -> Result<()> {
     let game_state = &mut ctx.accounts.game_state;
-    let player_state = &mut ctx.accounts.player_state;
-    let clock = Clock::get()?;
     
-    // Validate game is active
+    // Ranking happens once the game is completed and the pool is final
     require!(
-        game_state.status == GameStatus::Active,
+        game_state.status == GameStatus::Completed,
         GameError::InvalidGameStatus
     );
-    // check for all round is completed 
     require!(
-        player_state.all_round_completed,
-        GameError::PlayersNotEvaluated
+        !game_state.leaderboard_finalized,
+        GameError::LeaderboardAlreadyFinalized
     );
 
-    player_state.final_rank = Some(rank);
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        GameError::InvalidProfileAccount
+    );
 
-    let prize_amount = calculate_prize_amount(
-        rank,
-        game_state.prize_pool,
-        game_state.platform_fee_bps,
+    // the leaderboard is only complete once every player finished
+    require!(
+        game_state.completed_players == game_state.total_player,
+        GameError::PlayersNotEvaluated
     );
 
-    player_state.prize_amount = prize_amount;
+    for accounts in ctx.remaining_accounts.chunks_exact(2) {
+        let mut player_state = load_player_state(&accounts[0], game_id, ctx.program_id)?;
+        let mut player_profile = load_player_profile(&accounts[1], &player_state.player, ctx.program_id)?;
+
+        // check for all round is completed 
+        require!(
+            player_state.all_round_completed,
+            GameError::PlayersNotEvaluated
+        );
+        require!(
+            !player_state.ranked,
+            GameError::PlayerAlreadyRanked
+        );
+
+        let rank = game_state.leaderboard_rank(&player_state.player);
 
-    if rank == 1 {
-        game_state.top_scorer = Some(player_state.player);
-        game_state.higest_score = player_state.total_score;
+        // Survivor pays an equal share to every last survivor, ranked or not
+        let (prize_amount, won) = match game_state.game_mode {
+            GameMode::Classic => (
+                rank.map_or(0, |rank| calculate_prize_amount(
+                    rank,
+                    game_state.prize_pool,
+                    game_state.platform_fee_bps,
+                )),
+                rank == Some(1),
+            ),
+            GameMode::Survivor => {
+                let survivor = player_state.survival_level() == game_state.winning_round;
+                let prize_amount = if survivor {
+                    survivor_prize_amount(
+                        game_state.prize_pool,
+                        game_state.platform_fee_bps,
+                        game_state.winner_count,
+                    )
+                } else {
+                    0
+                };
+                (prize_amount, survivor)
+            }
+        };
+
+        player_profile.record_ranked(rank, player_state.total_score, won)?;
+
+        player_state.final_rank = rank;
+        player_state.ranked = true;
+        player_state.prize_amount = prize_amount;
+
+        game_state.ranked_players = game_state.ranked_players
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+
+        msg!("Player ranked!");
+        msg!("Player: {}", player_state.player);
+        msg!("Rank: {:?}", rank);
+        msg!("Total score: {}", player_state.total_score);
+        msg!("Prize: {}", prize_amount);
+
+        emit!(PlayerRanked {
+            game_id,
+            player: player_state.player,
+            rank,
+            total_score: player_state.total_score,
+            prize_amount,
+        });
+
+        player_state.exit(ctx.program_id)?;
+        player_profile.exit(ctx.program_id)?;
+    }
+
+    if game_state.ranked_players == game_state.total_player {
+        game_state.leaderboard_finalized = true;
+        msg!("Leaderboard finalized!");
+
+        emit!(LeaderboardFinalized {
+            game_id,
+            ranked_players: game_state.ranked_players,
+            top_scorer: game_state.top_scorer,
+            higest_score: game_state.higest_score,
+        });
     }
 
-    msg!("Player ranked!");
-    msg!("Player: {}", player_state.player);
-    msg!("Username: {}", player_state.username);
-    msg!("Rank: {}", rank);
-    msg!("Total score: {}", player_state.total_score);
-    msg!("Prize: {}", prize_amount);
     Ok(())
 }
 
+/// Load a writable PlayerProfile passed through remaining_accounts and check
+/// it is the PROFILE_SEED PDA of the player being ranked
+fn load_player_profile<'info>(
+    account_info: &'info AccountInfo<'info>,
+    player: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Account<'info, PlayerProfile>> {
+    require!(account_info.is_writable, GameError::InvalidProfileAccount);
+
+    // owner + discriminator are checked on deserialize
+    let player_profile: Account<'info, PlayerProfile> = Account::try_from(account_info)?;
+    require_keys_eq!(player_profile.player, *player, GameError::InvalidProfileAccount);
+
+    let expected = Pubkey::create_program_address(
+        &[PROFILE_SEED, player.as_ref(), &[player_profile.bump]],
+        program_id,
+    )
+    .map_err(|_| GameError::InvalidProfileAccount)?;
+    require_keys_eq!(expected, account_info.key(), GameError::InvalidProfileAccount);
+
+    Ok(player_profile)
+}
+
 fn calculate_prize_amount(rank: u16, prize_pool: u64, platform_fee_bps: u16) -> u64 {
-    // Calculate pool after platform fee
-    let platform_fee = (prize_pool as u128)
-        .checked_mul(platform_fee_bps as u128)
-        .and_then(|v| v.checked_div(BPS_DIVISOR as u128))
-        .and_then(|v| u64::try_from(v).ok())
-        .unwrap_or(0);
-    
-    let distributable_pool = prize_pool.saturating_sub(platform_fee);
+    let distributable_pool = distributable_pool(prize_pool, platform_fee_bps);
     
     // Calculate prize based on rank
     let prize_bps = match rank {
@@ -93,4 +175,23 @@ fn calculate_prize_amount(rank: u16, prize_pool: u64, platform_fee_bps: u16) ->
         .and_then(|v| v.checked_div(BPS_DIVISOR as u128))
         .and_then(|v| u64::try_from(v).ok())
         .unwrap_or(0)
-}
\ No newline at end of file
+}
+
+/// Pool left for players after the platform fee
+pub fn distributable_pool(prize_pool: u64, platform_fee_bps: u16) -> u64 {
+    let platform_fee = (prize_pool as u128)
+        .checked_mul(platform_fee_bps as u128)
+        .and_then(|v| v.checked_div(BPS_DIVISOR as u128))
+        .and_then(|v| u64::try_from(v).ok())
+        .unwrap_or(0);
+
+    prize_pool.saturating_sub(platform_fee)
+}
+
+/// Survivor: equal share of the distributable pool for each winner
+pub fn survivor_prize_amount(prize_pool: u64, platform_fee_bps: u16, winner_count: u16) -> u64 {
+    if winner_count == 0 {
+        return 0;
+    }
+    distributable_pool(prize_pool, platform_fee_bps) / winner_count as u64
+}
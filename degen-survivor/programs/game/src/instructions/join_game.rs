@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
+use crate::allowlist::require_game_access;
 use crate::constants::*;
 use crate::errors::GameError;
+use crate::events::PlayerJoined;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -24,15 +26,25 @@ pub struct JoinGame<'info> {
     #[account(
         init,
         payer = player,
-        space = PlayerState::SIZE,
+        space = PlayerState::space(game_state.total_round),
         seeds = [PLAYER_SEED, game_id.to_le_bytes().as_ref(), player.key().as_ref()],
         bump
     )]
     pub player_state: Account<'info, PlayerState>,
 
-    /// DEGEN token mint (from vault program)
-    #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
+    /// PlayerProfile account (created on the player's first game)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::SIZE,
+        seeds = [PROFILE_SEED, player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// Mint the game is played in
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// Player's DEGEN token account
     #[account(
@@ -40,23 +52,32 @@ pub struct JoinGame<'info> {
         token::mint = token_mint,
         token::authority = player,
     )]
-    pub player_token_account: Account<'info, TokenAccount>,
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Prize pool token account (receives entry fees)
     #[account(
         mut,
+        address = game_state.prize_pool_token_account,
         token::mint = token_mint,
     )]
-    pub prize_pool_token_account: Account<'info, TokenAccount>,
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Private games: the game access key co-signs the join
+    pub access_signer: Option<Signer<'info>>,
 
     /// Token program
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     
     /// System program
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<JoinGame>, game_id: u64, username: String) -> Result<()> {
+pub fn handler(
+    ctx: Context<JoinGame>,
+    game_id: u64,
+    username: String,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
     let game_state = &mut ctx.accounts.game_state;
     let player_state = &mut ctx.accounts.player_state;
     let clock = Clock::get()?;
@@ -82,48 +103,78 @@ pub fn handler(ctx: Context<JoinGame>, game_id: u64, username: String) -> Result
         GameError::RegistrationClosed
     );
     
+    // Private games only let in allowlisted wallets / access key holders
+    require_game_access(
+        game_state,
+        &ctx.accounts.player.key(),
+        &proof,
+        ctx.accounts.access_signer.as_ref().map(|signer| signer.key()),
+    )?;
+
     // Validate username
     require!(
         username.len() <= MAX_USERNAME_LENGTH,
         GameError::UsernameTooLong
     );
     
+    // Token-2022 transfer fees can withhold part of the entry fee,
+    // only what reaches the pool is credited
+    let pool_balance_before = ctx.accounts.prize_pool_token_account.amount;
+
     // Transfer entry fee from player to prize pool (DON'T BURN!)
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            TransferChecked {
                 from: ctx.accounts.player_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.prize_pool_token_account.to_account_info(),
                 authority: ctx.accounts.player.to_account_info(),
             },
         ),
         game_state.entry_fee,
+        ctx.accounts.token_mint.decimals,
     )?;
+
+    ctx.accounts.prize_pool_token_account.reload()?;
+    let entry_paid = ctx.accounts.prize_pool_token_account.amount
+        .checked_sub(pool_balance_before)
+        .ok_or(GameError::ArithmeticOverflow)?;
     
     // Initialize player state
     player_state.game_id = game_id;
     player_state.player = ctx.accounts.player.key();
     player_state.username = username.clone();
-    player_state.entry_slot = game_state.total_player as u64 + 1;
-    player_state.predection = [None, None, None, None, None];
-    player_state.scores = [0, 0, 0, 0, 0];
+    // unique join order, total_player goes back down when a player leaves
+    player_state.entry_slot = game_state.record_join(entry_paid)?;
+    player_state.predection = vec![None; game_state.total_round as usize];
+    player_state.scores = vec![0; game_state.total_round as usize];
     player_state.total_score = 0;
     player_state.round_evaluated = 0;
     player_state.all_round_completed = false;
     player_state.final_rank = None;
+    player_state.ranked = false;
     player_state.prize_amount = 0;
     player_state.prize_claimed = false;
     player_state.total_reponse_time = 0;
     player_state.avg_response_time = 0;
     player_state.first_prediction_ts = 0;
     player_state.bump = ctx.bumps.player_state;
-    
-    // Update game state
-    game_state.total_player += 1;
-    game_state.prize_pool = game_state.prize_pool
-        .checked_add(game_state.entry_fee)
-        .ok_or(GameError::ArithmeticOverflow)?;
+    player_state.refund_claimed = false;
+    player_state.eliminated_round = None;
+    player_state.lives_remaining = game_state.starting_lives;
+    player_state.streak = 0;
+    player_state.best_streak = 0;
+    player_state.entry_paid = entry_paid;
+
+    // Career stats, set up on the first join
+    let player_profile = &mut ctx.accounts.player_profile;
+    if player_profile.player == Pubkey::default() {
+        player_profile.player = ctx.accounts.player.key();
+        player_profile.created_at = clock.unix_timestamp;
+        player_profile.bump = ctx.bumps.player_profile;
+    }
+    player_profile.record_join(clock.unix_timestamp)?;
     
     msg!("Player joined game!");
     msg!("Game ID: {}", game_id);
@@ -132,6 +183,15 @@ pub fn handler(ctx: Context<JoinGame>, game_id: u64, username: String) -> Result
     msg!("Entry slot: {}", player_state.entry_slot);
     msg!("Total players: {}", game_state.total_player);
     msg!("Prize pool: {}", game_state.prize_pool);
+
+    emit!(PlayerJoined {
+        game_id,
+        player: player_state.player,
+        username,
+        entry_slot: player_state.entry_slot,
+        total_player: game_state.total_player,
+        prize_pool: game_state.prize_pool,
+    });
     
     Ok(())
 }
\ No newline at end of file
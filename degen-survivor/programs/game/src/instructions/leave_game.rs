@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::PlayerLeft;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct LeaveGame<'info> {
+    /// Player leaving the game, receives the PlayerState rent
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// GameState account (signs the transfer out of the pool)
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// PlayerState account (closed)
+    #[account(
+        mut,
+        close = player,
+        seeds = [PLAYER_SEED, game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        constraint = player_state.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    /// PlayerProfile of the player, the join is taken back
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// Mint the game is played in
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Prize pool token account holding the entry fees
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+        token::mint = token_mint,
+        token::authority = game_state,
+    )]
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Player's DEGEN token account
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = player,
+    )]
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<LeaveGame>, game_id: u64) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let clock = Clock::get()?;
+
+    require!(
+        game_state.status == GameStatus::Pending,
+        GameError::GameAlreadyStarted
+    );
+
+    // same cutoff as join_game, the roster is fixed once registration closes
+    let registration_deadline = game_state.start_time
+        .checked_sub(REGISTRATION_CLOSE_BEFORE_START)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    require!(
+        clock.unix_timestamp < registration_deadline,
+        GameError::RegistrationClosed
+    );
+
+    let refund_amount = ctx.accounts.player_state.entry_paid;
+
+    // Pool is owned by the game_state PDA
+    let game_id_bytes = game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.prize_pool_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.game_state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        refund_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let game_state = &mut ctx.accounts.game_state;
+    game_state.record_leave(refund_amount)?;
+
+    ctx.accounts.player_profile.record_leave()?;
+
+    msg!("Player left the game!");
+    msg!("Game ID: {}", game_id);
+    msg!("Player: {}", ctx.accounts.player.key());
+    msg!("Refund: {}", refund_amount);
+    msg!("Total players: {}", game_state.total_player);
+
+    emit!(PlayerLeft {
+        game_id,
+        player: ctx.accounts.player.key(),
+        refund: refund_amount,
+        total_player: game_state.total_player,
+        prize_pool: game_state.prize_pool,
+    });
+
+    Ok(())
+}
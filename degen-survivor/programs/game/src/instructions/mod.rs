@@ -6,6 +6,17 @@ pub mod evaluate_round;
 pub mod advance_round;
 pub mod finalize_leaderboard;
 pub mod claim_prize;
+pub mod resolve_round;
+pub mod cancel_game;
+pub mod claim_refund;
+pub mod complete_game;
+pub mod evaluate_round_batch;
+pub mod template;
+pub mod commit_prediction;
+pub mod reveal_prediction;
+pub mod leave_game;
+pub mod sponsor;
+pub mod close;
 
 pub use create_game::*;
 pub use join_game::*;
@@ -14,4 +25,15 @@ pub use submit_prediction::*;
 pub use evaluate_round::*;
 pub use advance_round::*;
 pub use finalize_leaderboard::*;
-pub use claim_prize::*;
\ No newline at end of file
+pub use claim_prize::*;
+pub use resolve_round::*;
+pub use cancel_game::*;
+pub use claim_refund::*;
+pub use complete_game::*;
+pub use evaluate_round_batch::*;
+pub use template::*;
+pub use commit_prediction::*;
+pub use reveal_prediction::*;
+pub use leave_game::*;
+pub use sponsor::*;
+pub use close::*;
\ No newline at end of file
@@ -0,0 +1,567 @@
+use anchor_lang::prelude::*;
+use oracle::constants::PRICE_SNAPSHOT_SEED;
+use oracle::state::{AssetType, PriceSnapshot, PriceStatus, SnapshotType};
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::RoundResolved;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, round_number: u8)]
+pub struct ResolveRound<'info> {
+    /// Anyone can resolve a round, the answer only depends on oracle data
+    pub resolver: Signer<'info>,
+
+    /// GameState account
+    #[account(
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// RoundResult being resolved
+    #[account(
+        mut,
+        seeds = [ROUND_RESULT_SEED, game_id.to_le_bytes().as_ref(), &[round_number]],
+        bump = round_result.bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+
+    /// BTC START snapshot (required for BtcOnly and BtcVsSol games)
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[round_number],
+            &[AssetType::BTC as u8],
+            &[SnapshotType::START as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = btc_start_snapshot.bump,
+    )]
+    pub btc_start_snapshot: Option<Account<'info, PriceSnapshot>>,
+
+    /// BTC MID snapshot (required for Trend rounds in games scored on BTC)
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[round_number],
+            &[AssetType::BTC as u8],
+            &[SnapshotType::MID as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = btc_mid_snapshot.bump,
+    )]
+    pub btc_mid_snapshot: Option<Account<'info, PriceSnapshot>>,
+
+    /// BTC END snapshot
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[round_number],
+            &[AssetType::BTC as u8],
+            &[SnapshotType::END as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = btc_end_snapshot.bump,
+    )]
+    pub btc_end_snapshot: Option<Account<'info, PriceSnapshot>>,
+
+    /// SOL START snapshot (required for SolOnly and BtcVsSol games)
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[round_number],
+            &[AssetType::SOL as u8],
+            &[SnapshotType::START as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = sol_start_snapshot.bump,
+    )]
+    pub sol_start_snapshot: Option<Account<'info, PriceSnapshot>>,
+
+    /// SOL MID snapshot (required for Trend rounds in games scored on SOL)
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[round_number],
+            &[AssetType::SOL as u8],
+            &[SnapshotType::MID as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = sol_mid_snapshot.bump,
+    )]
+    pub sol_mid_snapshot: Option<Account<'info, PriceSnapshot>>,
+
+    /// SOL END snapshot
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[round_number],
+            &[AssetType::SOL as u8],
+            &[SnapshotType::END as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = sol_end_snapshot.bump,
+    )]
+    pub sol_end_snapshot: Option<Account<'info, PriceSnapshot>>,
+}
+
+/// Start and end price of one asset for a round (micro-dollars),
+/// plus the mid-round price when a MID snapshot was passed in
+#[derive(Clone, Copy, Debug)]
+pub struct PriceMove {
+    pub start: u64,
+    pub mid: Option<u64>,
+    pub end: u64,
+}
+
+impl PriceMove {
+    pub fn change(&self) -> Result<i64> {
+        let start = i64::try_from(self.start).map_err(|_| GameError::ArithmeticOverflow)?;
+        let end = i64::try_from(self.end).map_err(|_| GameError::ArithmeticOverflow)?;
+        let change = end.checked_sub(start).ok_or(GameError::ArithmeticOverflow)?;
+        Ok(change)
+    }
+}
+
+pub fn handler(ctx: Context<ResolveRound>, game_id: u64, round_number: u8) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let round_result = &mut ctx.accounts.round_result;
+    let clock = Clock::get()?;
+
+    require!(
+        game_state.status == GameStatus::Active,
+        GameError::InvalidGameStatus
+    );
+
+    // check round is ended
+    require!(
+        clock.unix_timestamp > round_result.round_end_ts,
+        GameError::RoundNotEnded
+    );
+
+    require!(
+        round_result.correct_answer.is_none(),
+        GameError::RoundAlreadyResolved
+    );
+
+    let btc_move = read_price_move(
+        ctx.accounts.btc_start_snapshot.as_deref(),
+        ctx.accounts.btc_mid_snapshot.as_deref(),
+        ctx.accounts.btc_end_snapshot.as_deref(),
+        round_result,
+    )?;
+    let sol_move = read_price_move(
+        ctx.accounts.sol_start_snapshot.as_deref(),
+        ctx.accounts.sol_mid_snapshot.as_deref(),
+        ctx.accounts.sol_end_snapshot.as_deref(),
+        round_result,
+    )?;
+
+    // every asset the game trades must be priced
+    require_priced_assets(
+        game_state.game_type,
+        btc_move.is_some(),
+        sol_move.is_some(),
+    )?;
+
+    let correct_answer = derive_correct_answer(
+        round_result,
+        game_state.game_type,
+        btc_move,
+        sol_move,
+    )?;
+
+    round_result.start_price_btc = btc_move.map(|m| m.start);
+    round_result.mid_price_btc = btc_move.and_then(|m| m.mid);
+    round_result.end_price_btc = btc_move.map(|m| m.end);
+    round_result.start_price_sol = sol_move.map(|m| m.start);
+    round_result.mid_price_sol = sol_move.and_then(|m| m.mid);
+    round_result.end_price_sol = sol_move.map(|m| m.end);
+    round_result.price_change_btc = btc_move.map(|m| m.change()).transpose()?.unwrap_or(0);
+    round_result.price_change_sol = sol_move.map(|m| m.change()).transpose()?.unwrap_or(0);
+    round_result.correct_answer = Some(correct_answer);
+    round_result.evaluation_ts = Some(clock.unix_timestamp);
+
+    msg!("Round resolved from oracle!");
+    msg!("Game ID: {}", game_id);
+    msg!("Round: {}", round_number);
+    msg!("BTC change: {}", round_result.price_change_btc);
+    msg!("SOL change: {}", round_result.price_change_sol);
+    msg!("Correct answer: {:?}", correct_answer);
+
+    emit!(RoundResolved {
+        game_id,
+        round_number,
+        correct_answer,
+        price_change_btc: round_result.price_change_btc,
+        price_change_sol: round_result.price_change_sol,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Every asset traded by the game type must have a price
+pub fn require_priced_assets(game_type: GameType, has_btc: bool, has_sol: bool) -> Result<()> {
+    let priced = match game_type {
+        GameType::BtcOnly => has_btc,
+        GameType::SolOnly => has_sol,
+        GameType::BtcVsSol => has_btc && has_sol,
+    };
+    require!(priced, GameError::MissingPriceSnapshot);
+    Ok(())
+}
+
+/// Magnitude bucket bounds of the asset the game scores single asset rounds on,
+/// as configured by its template
+pub fn magnitude_buckets(game_state: &GameState) -> [u64; 3] {
+    match game_state.game_type {
+        GameType::SolOnly => game_state.sol_magnitude_buckets,
+        GameType::BtcOnly | GameType::BtcVsSol => game_state.btc_magnitude_buckets,
+    }
+}
+
+/// Bounds published on a new RoundResult, None for other round types
+pub fn round_magnitude_buckets(round_type: RoundType, game_state: &GameState) -> Option<[u64; 3]> {
+    (round_type == RoundType::Magnitude).then(|| magnitude_buckets(game_state))
+}
+
+/// Zone bounds centered on `start_price`, `width_bps` apart
+pub fn range_zones(start_price: u64, width_bps: u16) -> [u64; 3] {
+    let width = ((start_price as u128) * (width_bps as u128) / (BPS_DIVISOR as u128)) as u64;
+    [
+        start_price.saturating_sub(width),
+        start_price,
+        start_price.saturating_add(width),
+    ]
+}
+
+/// Zone bounds published on a new RoundResult, None for other round types
+pub fn round_range_zones(
+    round_type: RoundType,
+    game_state: &GameState,
+    start_price_btc: Option<u64>,
+    start_price_sol: Option<u64>,
+) -> Option<[u64; 3]> {
+    if round_type != RoundType::Range {
+        return None;
+    }
+
+    let start_price = match game_state.game_type {
+        GameType::SolOnly => start_price_sol,
+        GameType::BtcOnly | GameType::BtcVsSol => start_price_btc,
+    }?;
+    Some(range_zones(start_price, game_state.range_zone_width_bps))
+}
+
+/// Read the START snapshot of a round that opens now
+pub fn read_start_price(snapshot: Option<&PriceSnapshot>, now: i64) -> Result<Option<u64>> {
+    let snapshot = match snapshot {
+        Some(snapshot) => snapshot,
+        None => return Ok(None),
+    };
+
+    require!(
+        snapshot.status == PriceStatus::Valid && snapshot.price_normalized > 0,
+        GameError::InvalidPriceSnapshot
+    );
+
+    let age = now
+        .checked_sub(snapshot.snapshot_time)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    require!(
+        age.abs() <= SNAPSHOT_TOLERANCE_SECONDS,
+        GameError::InvalidPriceSnapshot
+    );
+
+    Ok(Some(snapshot.price_normalized))
+}
+
+/// Validate a START/END snapshot pair and turn it into a price move.
+/// Returns None when neither snapshot was passed in, MID is optional.
+pub fn read_price_move(
+    start: Option<&PriceSnapshot>,
+    mid: Option<&PriceSnapshot>,
+    end: Option<&PriceSnapshot>,
+    round_result: &RoundResult,
+) -> Result<Option<PriceMove>> {
+    let (start, end) = match (start, end) {
+        (Some(start), Some(end)) => (start, end),
+        (None, None) => return Ok(None),
+        _ => return err!(GameError::MissingPriceSnapshot),
+    };
+
+    require!(
+        start.status == PriceStatus::Valid && end.status == PriceStatus::Valid,
+        GameError::InvalidPriceSnapshot
+    );
+    require!(start.price_normalized > 0, GameError::InvalidPriceSnapshot);
+
+    // START must be taken around round start, END at or just after round end
+    let start_offset = start
+        .snapshot_time
+        .checked_sub(round_result.round_start_ts)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    require!(
+        start_offset.abs() <= SNAPSHOT_TOLERANCE_SECONDS,
+        GameError::InvalidPriceSnapshot
+    );
+
+    let end_offset = end
+        .snapshot_time
+        .checked_sub(round_result.round_end_ts)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    require!(
+        (0..=SNAPSHOT_TOLERANCE_SECONDS).contains(&end_offset),
+        GameError::InvalidPriceSnapshot
+    );
+
+    Ok(Some(PriceMove {
+        start: start.price_normalized,
+        mid: read_mid_price(mid, round_result)?,
+        end: end.price_normalized,
+    }))
+}
+
+/// Validate a MID snapshot, it must be taken around the middle of the round
+pub fn read_mid_price(mid: Option<&PriceSnapshot>, round_result: &RoundResult) -> Result<Option<u64>> {
+    let mid = match mid {
+        Some(mid) => mid,
+        None => return Ok(None),
+    };
+
+    require!(
+        mid.status == PriceStatus::Valid && mid.price_normalized > 0,
+        GameError::InvalidPriceSnapshot
+    );
+
+    let half_round = round_result
+        .round_end_ts
+        .checked_sub(round_result.round_start_ts)
+        .ok_or(GameError::ArithmeticOverflow)?
+        / 2;
+    let mid_offset = mid
+        .snapshot_time
+        .checked_sub(round_result.round_start_ts)
+        .and_then(|v| v.checked_sub(half_round))
+        .ok_or(GameError::ArithmeticOverflow)?;
+    require!(
+        mid_offset.abs() <= SNAPSHOT_TOLERANCE_SECONDS,
+        GameError::InvalidPriceSnapshot
+    );
+
+    Ok(Some(mid.price_normalized))
+}
+
+/// Work out the winning choice for a round from the observed price moves.
+/// Single asset round types use SOL in SolOnly games and BTC otherwise.
+///
+/// Ties: a PriceDirection round with no price change resolves to Flat, which
+/// no prediction matches, so neither side is favoured. A move landing exactly
+/// on a Magnitude or Range bound belongs to the bucket or zone above it, so an
+/// unchanged price in a Range round is ZoneC. A Trend half that did not move
+/// counts as Lower. Comperative rounds have Equal for even moves.
+pub fn derive_correct_answer(
+    round_result: &RoundResult,
+    game_type: GameType,
+    btc_move: Option<PriceMove>,
+    sol_move: Option<PriceMove>,
+) -> Result<PredectionChoice> {
+    let primary = match game_type {
+        GameType::SolOnly => sol_move,
+        GameType::BtcOnly | GameType::BtcVsSol => btc_move,
+    }
+    .ok_or(GameError::MissingPriceSnapshot)?;
+
+    let answer = match round_result.round_type {
+        RoundType::PriceDirection => {
+            match primary.change()? {
+                change if change > 0 => PredectionChoice::Up,
+                change if change < 0 => PredectionChoice::Down,
+                _ => PredectionChoice::Flat,
+            }
+        }
+
+        RoundType::Magnitude => {
+            // bounds are published when the round opens
+            let [a_max, b_max, c_max] = round_result
+                .magnitude_buckets
+                .ok_or(GameError::UnsupportedRoundType)?;
+
+            let moved = primary.change()?.unsigned_abs();
+            if moved < a_max {
+                PredectionChoice::RangeA
+            } else if moved < b_max {
+                PredectionChoice::RangeB
+            } else if moved < c_max {
+                PredectionChoice::RangeC
+            } else {
+                PredectionChoice::RangeD
+            }
+        }
+
+        RoundType::Comperative => {
+            let btc = btc_move.ok_or(GameError::MissingPriceSnapshot)?;
+            let sol = sol_move.ok_or(GameError::MissingPriceSnapshot)?;
+
+            // compare percentage moves without rounding:
+            // btc_change / btc_start  vs  sol_change / sol_start
+            let btc_scaled = (btc.change()? as i128) * (sol.start as i128);
+            let sol_scaled = (sol.change()? as i128) * (btc.start as i128);
+
+            if btc_scaled > sol_scaled {
+                PredectionChoice::BtcMore
+            } else if sol_scaled > btc_scaled {
+                PredectionChoice::SolMore
+            } else {
+                PredectionChoice::Equal
+            }
+        }
+
+        RoundType::Range => {
+            // bounds are published when the round opens
+            let [a_max, b_max, c_max] = round_result
+                .range_zones
+                .ok_or(GameError::UnsupportedRoundType)?;
+
+            // each zone includes its lower bound, like the magnitude buckets
+            if primary.end < a_max {
+                PredectionChoice::ZoneA
+            } else if primary.end < b_max {
+                PredectionChoice::ZoneB
+            } else if primary.end < c_max {
+                PredectionChoice::ZoneC
+            } else {
+                PredectionChoice::ZoneD
+            }
+        }
+
+        RoundType::Trend => {
+            // first half (start -> mid) then second half (mid -> end)
+            let mid = primary.mid.ok_or(GameError::MissingPriceSnapshot)?;
+
+            match (mid > primary.start, primary.end > mid) {
+                (true, true) => PredectionChoice::HigherHigher,
+                (false, false) => PredectionChoice::LowerLower,
+                (true, false) => PredectionChoice::HigherLower,
+                (false, true) => PredectionChoice::LowerHigher,
+            }
+        }
+    };
+
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(round_type: RoundType) -> RoundResult {
+        RoundResult::test_round(round_type)
+    }
+
+    fn price_move(start: u64, end: u64) -> Option<PriceMove> {
+        Some(PriceMove { start, mid: None, end })
+    }
+
+    fn answer(round_result: &RoundResult, btc: Option<PriceMove>, sol: Option<PriceMove>) -> PredectionChoice {
+        derive_correct_answer(round_result, GameType::BtcVsSol, btc, sol).unwrap()
+    }
+
+    #[test]
+    fn price_direction_follows_the_move() {
+        let rr = round(RoundType::PriceDirection);
+        assert_eq!(answer(&rr, price_move(100, 101), None), PredectionChoice::Up);
+        assert_eq!(answer(&rr, price_move(100, 99), None), PredectionChoice::Down);
+    }
+
+    #[test]
+    fn flat_price_direction_favours_no_side() {
+        let rr = round(RoundType::PriceDirection);
+        let flat = answer(&rr, price_move(100, 100), None);
+        assert_eq!(flat, PredectionChoice::Flat);
+        assert!(!RoundType::PriceDirection.allows(flat));
+    }
+
+    #[test]
+    fn magnitude_bound_belongs_to_the_bucket_above() {
+        let mut rr = round(RoundType::Magnitude);
+        rr.magnitude_buckets = Some([10, 20, 30]);
+        let start = 1_000;
+        assert_eq!(answer(&rr, price_move(start, start + 9), None), PredectionChoice::RangeA);
+        assert_eq!(answer(&rr, price_move(start, start + 10), None), PredectionChoice::RangeB);
+        assert_eq!(answer(&rr, price_move(start, start - 20), None), PredectionChoice::RangeC);
+        assert_eq!(answer(&rr, price_move(start, start + 30), None), PredectionChoice::RangeD);
+    }
+
+    #[test]
+    fn magnitude_needs_published_buckets() {
+        let rr = round(RoundType::Magnitude);
+        assert!(derive_correct_answer(&rr, GameType::BtcOnly, price_move(100, 101), None).is_err());
+    }
+
+    #[test]
+    fn range_bound_belongs_to_the_zone_above() {
+        let mut rr = round(RoundType::Range);
+        rr.range_zones = Some([990, 1_000, 1_010]);
+        let start = 1_000;
+        assert_eq!(answer(&rr, price_move(start, 989), None), PredectionChoice::ZoneA);
+        assert_eq!(answer(&rr, price_move(start, 990), None), PredectionChoice::ZoneB);
+        assert_eq!(answer(&rr, price_move(start, 999), None), PredectionChoice::ZoneB);
+        assert_eq!(answer(&rr, price_move(start, 1_000), None), PredectionChoice::ZoneC);
+        assert_eq!(answer(&rr, price_move(start, 1_009), None), PredectionChoice::ZoneC);
+        assert_eq!(answer(&rr, price_move(start, 1_010), None), PredectionChoice::ZoneD);
+    }
+
+    #[test]
+    fn range_zones_are_centered_on_the_start_price() {
+        // 10 bps of 100_000 puts the bounds at 99_900, 100_000 and 100_100
+        assert_eq!(range_zones(100_000, 10), [99_900, 100_000, 100_100]);
+    }
+
+    #[test]
+    fn range_needs_published_zones() {
+        let rr = round(RoundType::Range);
+        assert!(derive_correct_answer(&rr, GameType::BtcOnly, price_move(100, 101), None).is_err());
+    }
+
+    #[test]
+    fn comperative_compares_percentage_moves() {
+        let rr = round(RoundType::Comperative);
+        // +1% vs +1%
+        assert_eq!(answer(&rr, price_move(100, 101), price_move(200, 202)), PredectionChoice::Equal);
+        // +2% vs +1%
+        assert_eq!(answer(&rr, price_move(100, 102), price_move(200, 202)), PredectionChoice::BtcMore);
+        assert_eq!(answer(&rr, price_move(100, 100), price_move(200, 202)), PredectionChoice::SolMore);
+    }
+
+    #[test]
+    fn trend_flat_half_counts_as_lower() {
+        let rr = round(RoundType::Trend);
+        let trend = |mid: u64, end: u64| answer(&rr, Some(PriceMove { start: 100, mid: Some(mid), end }), None);
+        assert_eq!(trend(101, 102), PredectionChoice::HigherHigher);
+        assert_eq!(trend(99, 98), PredectionChoice::LowerLower);
+        assert_eq!(trend(101, 100), PredectionChoice::HigherLower);
+        assert_eq!(trend(100, 101), PredectionChoice::LowerHigher);
+    }
+
+    #[test]
+    fn price_change_rejects_prices_beyond_i64() {
+        assert_eq!(PriceMove { start: 100, mid: None, end: 40 }.change().unwrap(), -60);
+        assert!(PriceMove { start: 1, mid: None, end: u64::MAX }.change().is_err());
+    }
+
+    #[test]
+    fn trend_needs_a_mid_price() {
+        let rr = round(RoundType::Trend);
+        assert!(derive_correct_answer(&rr, GameType::BtcOnly, price_move(100, 101), None).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::PredictionRevealed;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, round_number: u8)]
+pub struct RevealPrediction<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_SEED, game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        constraint = player_state.player == player.key() @ GameError::Unauthorized,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    #[account(
+        seeds = [ROUND_RESULT_SEED, game_id.to_le_bytes().as_ref(), &[round_number]],
+        bump = round_result.bump,
+    )]
+    pub round_result: Account<'info, RoundResult>,
+}
+
+pub fn handler(
+    ctx: Context<RevealPrediction>,
+    game_id: u64,
+    round_number: u8,
+    choice: PredectionChoice,
+    salt: [u8; 32],
+) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let player_state = &mut ctx.accounts.player_state;
+    let round_result = &ctx.accounts.round_result;
+    let clock = Clock::get()?;
+
+    require!(game_state.commit_reveal, GameError::CommitRevealDisabled);
+
+    // reveals open once the round is over and close with the reveal window
+    let reveal_end = round_result
+        .round_end_ts
+        .checked_add(REVEAL_WINDOW_SECONDS)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    require!(
+        clock.unix_timestamp >= round_result.round_end_ts && clock.unix_timestamp <= reveal_end,
+        GameError::RevealWindowClosed
+    );
+
+    // a scored round can no longer change
+    require!(
+        player_state.round_evaluated < round_number,
+        GameError::AlreadyEvaluated
+    );
+
+    require!(
+        round_number >= 1 && round_number <= game_state.total_round,
+        GameError::InvalidRoundNumber
+    );
+
+    let prediction = player_state.predection[(round_number - 1) as usize]
+        .as_mut()
+        .ok_or(GameError::NothingToReveal)?;
+
+    let commitment = prediction.commitment.ok_or(GameError::NothingToReveal)?;
+    require!(prediction.choice.is_none(), GameError::AlreadyPredicted);
+
+    require!(
+        commitment_hash(choice, &salt, &ctx.accounts.player.key(), game_id, round_number) == commitment,
+        GameError::InvalidReveal
+    );
+
+    // an illegal choice stays unrevealed and is scored as wrong
+    require!(
+        round_result.round_type.allows(choice),
+        GameError::InvalidPredictionChoice
+    );
+
+    prediction.choice = Some(choice);
+
+    msg!("Prediction revealed!");
+    msg!("Player: {}", ctx.accounts.player.key());
+    msg!("Round: {}", round_number);
+    msg!("Choice: {:?}", choice);
+
+    emit!(PredictionRevealed {
+        game_id,
+        player: ctx.accounts.player.key(),
+        round_number,
+        choice,
+    });
+
+    Ok(())
+}
+
+/// sha256(choice || salt || player || game_id || round_number), choice is the
+/// single byte enum index and game_id is little endian. Binding the player,
+/// game and round stops a commitment from being copied and revealed elsewhere.
+pub fn commitment_hash(choice: PredectionChoice, salt: &[u8; 32], player: &Pubkey, game_id: u64, round_number: u8) -> [u8; 32] {
+    hashv(&[
+        &[choice as u8],
+        salt.as_ref(),
+        player.as_ref(),
+        &game_id.to_le_bytes(),
+        &[round_number],
+    ])
+    .to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SALT: [u8; 32] = [7; 32];
+
+    #[test]
+    fn commitment_matches_the_documented_layout() {
+        let player = Pubkey::new_unique();
+        let mut preimage = vec![PredectionChoice::Up as u8];
+        preimage.extend_from_slice(&SALT);
+        preimage.extend_from_slice(player.as_ref());
+        preimage.extend_from_slice(&42u64.to_le_bytes());
+        preimage.push(3);
+
+        assert_eq!(
+            commitment_hash(PredectionChoice::Up, &SALT, &player, 42, 3),
+            hashv(&[&preimage]).to_bytes()
+        );
+    }
+
+    #[test]
+    fn commitment_is_bound_to_player_game_and_round() {
+        let player = Pubkey::new_unique();
+        let commitment = commitment_hash(PredectionChoice::Up, &SALT, &player, 42, 3);
+
+        assert_ne!(commitment, commitment_hash(PredectionChoice::Up, &SALT, &Pubkey::new_unique(), 42, 3));
+        assert_ne!(commitment, commitment_hash(PredectionChoice::Up, &SALT, &player, 43, 3));
+        assert_ne!(commitment, commitment_hash(PredectionChoice::Up, &SALT, &player, 42, 4));
+        assert_ne!(commitment, commitment_hash(PredectionChoice::Down, &SALT, &player, 42, 3));
+    }
+}
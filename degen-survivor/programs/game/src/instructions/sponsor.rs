@@ -0,0 +1,295 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use vault::constants::GLOBAL_VAULT_SEED;
+use vault::state::GlobalVault;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::{GuaranteedPrizeSet, PoolSponsored, SponsorshipRefunded};
+use crate::state::*;
+
+/// Add DEGEN to a game's prize pool
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct SponsorPool<'info> {
+    /// Wallet sponsoring the game
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// GameState account
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Sponsorship record, one per sponsor and game
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = Sponsorship::SIZE,
+        seeds = [SPONSOR_SEED, game_id.to_le_bytes().as_ref(), sponsor.key().as_ref()],
+        bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    /// Mint the game is played in
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Sponsor's DEGEN token account
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = sponsor,
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Prize pool token account receiving the top-up
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+        token::mint = token_mint,
+    )]
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn sponsor_pool(ctx: Context<SponsorPool>, game_id: u64, amount: u64) -> Result<()> {
+    require!(amount > 0, GameError::InvalidAmount);
+
+    // the pool is split when the leaderboard is finalized
+    require!(
+        matches!(ctx.accounts.game_state.status, GameStatus::Pending | GameStatus::Active),
+        GameError::InvalidGameStatus
+    );
+
+    // only what reaches the pool is credited (Token-2022 transfer fees)
+    let pool_balance_before = ctx.accounts.prize_pool_token_account.amount;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sponsor_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.prize_pool_token_account.to_account_info(),
+                authority: ctx.accounts.sponsor.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    ctx.accounts.prize_pool_token_account.reload()?;
+    let amount = ctx.accounts.prize_pool_token_account.amount
+        .checked_sub(pool_balance_before)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    let game_state = &mut ctx.accounts.game_state;
+    let sponsorship = &mut ctx.accounts.sponsorship;
+
+    if sponsorship.sponsor == Pubkey::default() {
+        sponsorship.game_id = game_id;
+        sponsorship.sponsor = ctx.accounts.sponsor.key();
+        sponsorship.amount = 0;
+        sponsorship.refunded = false;
+        sponsorship.bump = ctx.bumps.sponsorship;
+        game_state.sponsor_count = game_state
+            .sponsor_count
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+    }
+
+    sponsorship.amount = sponsorship
+        .amount
+        .checked_add(amount)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    game_state.sponsored_amount = game_state
+        .sponsored_amount
+        .checked_add(amount)
+        .ok_or(GameError::ArithmeticOverflow)?;
+    game_state.prize_pool = game_state
+        .prize_pool
+        .checked_add(amount)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Prize pool sponsored!");
+    msg!("Game ID: {}", game_id);
+    msg!("Sponsor: {}", sponsorship.sponsor);
+    msg!("Amount: {}", amount);
+    msg!("Prize pool: {}", game_state.prize_pool);
+
+    emit!(PoolSponsored {
+        game_id,
+        sponsor: sponsorship.sponsor,
+        amount,
+        total_sponsored: game_state.sponsored_amount,
+        prize_pool: game_state.prize_pool,
+    });
+
+    Ok(())
+}
+
+/// Set the prize a promoted game advertises, the treasury covers any
+/// shortfall when the game starts
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct SetGuaranteedPrize<'info> {
+    /// Platform admin (GlobalVault admin)
+    pub admin: Signer<'info>,
+
+    /// GlobalVault PDA, source of the platform admin
+    #[account(
+        seeds = [GLOBAL_VAULT_SEED],
+        seeds::program = vault::ID,
+        bump = global_vault.bump,
+        constraint = global_vault.admin == admin.key() @ GameError::Unauthorized,
+    )]
+    pub global_vault: Account<'info, GlobalVault>,
+
+    /// GameState account
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+pub fn set_guaranteed_prize(ctx: Context<SetGuaranteedPrize>, game_id: u64, amount: u64) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+
+    require!(
+        game_state.status == GameStatus::Pending,
+        GameError::GameAlreadyStarted
+    );
+
+    game_state.guaranteed_prize = amount;
+
+    msg!("Guaranteed prize set!");
+    msg!("Game ID: {}", game_id);
+    msg!("Guaranteed prize: {}", amount);
+
+    emit!(GuaranteedPrizeSet {
+        game_id,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Sponsor takes their top-up back from a cancelled game
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ClaimSponsorRefund<'info> {
+    /// Sponsor getting the DEGEN back
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// GameState account (signs the transfer out of the pool)
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Sponsorship record
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, game_id.to_le_bytes().as_ref(), sponsor.key().as_ref()],
+        bump = sponsorship.bump,
+        constraint = sponsorship.sponsor == sponsor.key() @ GameError::Unauthorized,
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    /// Mint the game is played in
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Prize pool token account holding the top-up
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+        token::mint = token_mint,
+        token::authority = game_state,
+    )]
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Sponsor's DEGEN token account
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = sponsor,
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn claim_sponsor_refund(ctx: Context<ClaimSponsorRefund>, game_id: u64) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let sponsorship = &ctx.accounts.sponsorship;
+
+    require!(
+        game_state.status == GameStatus::Cancelled,
+        GameError::InvalidGameStatus
+    );
+
+    require!(
+        !sponsorship.refunded,
+        GameError::SponsorshipAlreadyRefunded
+    );
+
+    let refund_amount = game_state.refund_share(sponsorship.amount)?;
+
+    // Pool is owned by the game_state PDA
+    let game_id_bytes = game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.prize_pool_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.sponsor_token_account.to_account_info(),
+                authority: ctx.accounts.game_state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        refund_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let game_state = &mut ctx.accounts.game_state;
+    let sponsorship = &mut ctx.accounts.sponsorship;
+
+    sponsorship.refunded = true;
+    game_state.prize_pool = game_state
+        .prize_pool
+        .checked_sub(refund_amount)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Sponsorship refunded!");
+    msg!("Game ID: {}", game_id);
+    msg!("Sponsor: {}", sponsorship.sponsor);
+    msg!("Refund: {}", refund_amount);
+
+    emit!(SponsorshipRefunded {
+        game_id,
+        sponsor: sponsorship.sponsor,
+        amount: refund_amount,
+    });
+
+    Ok(())
+}
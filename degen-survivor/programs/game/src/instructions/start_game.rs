@@ -1,33 +1,97 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use oracle::constants::PRICE_SNAPSHOT_SEED;
+use oracle::state::{AssetType, PriceSnapshot, SnapshotType};
+use vault::program::Vault;
+use vault::state::Treasury;
 
 use crate::constants::*;
+use crate::crank::pay_crank_reward;
+use crate::treasury::cover_guaranteed_prize;
 use crate::errors::GameError;
+use crate::events::{GameStarted, RoundStarted};
+use crate::instructions::resolve_round::{read_start_price, require_priced_assets, round_magnitude_buckets, round_range_zones};
 use crate::state::*;
 
 #[derive(Accounts)]
 #[instruction(game_id: u64)]
 pub struct  StartGame <'info> {
+    /// Anyone can start the game once start_time has passed
     #[account(mut)]
-    pub creator : Signer<'info>,
+    pub caller : Signer<'info>,
     #[account(
         mut,
         seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
         bump = game_state.bump,
-        constraint = game_state.creator == creator.key() @ GameError::Unauthorized,
     )]
     pub game_state : Account<'info,GameState>,
     #[account(
         init ,
-        payer = creator,
+        payer = caller,
         space = RoundResult::SIZE,
         seeds = [ROUND_RESULT_SEED,game_id.to_le_bytes().as_ref() , &[1u8]],
         bump ,
     )]
     pub round_result : Account<'info,RoundResult>,
 
+    /// BTC START snapshot for round 1
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[1u8],
+            &[AssetType::BTC as u8],
+            &[SnapshotType::START as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = btc_start_snapshot.bump,
+    )]
+    pub btc_start_snapshot : Option<Account<'info,PriceSnapshot>>,
+
+    /// SOL START snapshot for round 1
+    #[account(
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[1u8],
+            &[AssetType::SOL as u8],
+            &[SnapshotType::START as u8],
+        ],
+        seeds::program = oracle::ID,
+        bump = sol_start_snapshot.bump,
+    )]
+    pub sol_start_snapshot : Option<Account<'info,PriceSnapshot>>,
+
+    /// Prize pool token account (needed to pay the crank reward or cover a guarantee)
+    #[account(
+        mut,
+        address = game_state.prize_pool_token_account,
+    )]
+    pub prize_pool_token_account : Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Caller's token account receiving the crank reward
+    #[account(mut)]
+    pub caller_token_account : Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Vault treasury of the game's mint, covers guaranteed prizes
+    #[account(mut)]
+    pub treasury : Option<Account<'info, Treasury>>,
+
+    /// Token account of the vault treasury
+    #[account(mut)]
+    pub treasury_token_account : Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub vault_program : Option<Program<'info, Vault>>,
+
+    /// Mint the game is played in (needed for token transfers)
+    #[account(address = game_state.token_mint @ GameError::InvalidTokenMint)]
+    pub token_mint : Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program : Option<Interface<'info, TokenInterface>>,
+
     pub system_program : Program<'info,System>,
 }
-pub fn handler(ctx:Context<StartGame>,start_btc_price:Option<u64>, start_sol_price:Option<u64>,game_id : u64)->Result<()>{
+pub fn handler(ctx:Context<StartGame>,game_id : u64)->Result<()>{
     
     let game_state  = &mut ctx.accounts.game_state;
     let round_result = &mut ctx.accounts.round_result;
@@ -42,10 +106,15 @@ pub fn handler(ctx:Context<StartGame>,start_btc_price:Option<u64>, start_sol_pri
         GameError::GameNotStarted
     );
     // check for total player is greater than min player 
-    require!(game_state.total_player >= MIN_PLAYER,
+    require!(game_state.total_player >= game_state.min_player,
         GameError::InsufficientPlayers
     );
 
+    // round 1 start prices come from the oracle
+    let start_btc_price = read_start_price(ctx.accounts.btc_start_snapshot.as_deref(), clock.unix_timestamp)?;
+    let start_sol_price = read_start_price(ctx.accounts.sol_start_snapshot.as_deref(), clock.unix_timestamp)?;
+    require_priced_assets(game_state.game_type, start_btc_price.is_some(), start_sol_price.is_some())?;
+
     // update game state result 
 
     game_state.status = GameStatus::Active;
@@ -61,17 +130,43 @@ pub fn handler(ctx:Context<StartGame>,start_btc_price:Option<u64>, start_sol_pri
     round_result.round_type = game_state.round_types[0].clone();
     round_result.start_price_btc = start_btc_price ;
     round_result.start_price_sol = start_sol_price;
+    round_result.mid_price_btc = None;
+    round_result.mid_price_sol = None;
     round_result.end_price_btc = None ; 
     round_result.end_price_sol = None;
     round_result.price_change_btc = 0 ;
     round_result.price_change_sol = 0 ;
     round_result.round_start_ts = clock.unix_timestamp;
-    round_result.round_end_ts = clock.unix_timestamp + ROUND_DURATION_SECONDS;
+    round_result.round_end_ts = clock.unix_timestamp + game_state.round_duration_seconds;
     round_result.evaluation_ts = None;
     round_result.total_predection = 0;
     round_result.correct_predection =0 ;
     round_result.wrong_predection = 0;
+    round_result.knocked_out = 0;
+    round_result.magnitude_buckets = round_magnitude_buckets(round_result.round_type, game_state);
+    round_result.range_zones = round_range_zones(round_result.round_type, game_state, start_btc_price, start_sol_price);
     round_result.bump = ctx.bumps.round_result;
+    round_result.payer = ctx.accounts.caller.key();
+
+    // top up before the crank reward so promoted games start at the advertised pool
+    cover_guaranteed_prize(
+        game_state,
+        ctx.accounts.prize_pool_token_account.as_mut(),
+        ctx.accounts.treasury.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        ctx.accounts.token_mint.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+        ctx.accounts.vault_program.as_ref(),
+    )?;
+
+    pay_crank_reward(
+        game_state,
+        &ctx.accounts.caller,
+        ctx.accounts.prize_pool_token_account.as_ref(),
+        ctx.accounts.caller_token_account.as_ref(),
+        ctx.accounts.token_mint.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
 
     msg!("Game started!");
     msg!("Game ID: {}", game_id);
@@ -79,6 +174,25 @@ pub fn handler(ctx:Context<StartGame>,start_btc_price:Option<u64>, start_sol_pri
     msg!("Prize pool: {}", game_state.prize_pool);
     msg!("Round 1 start price BTC: {:?}", start_btc_price);
     msg!("Round 1 start price SOL: {:?}", start_sol_price);
+
+    emit!(GameStarted {
+        game_id,
+        started_by: ctx.accounts.caller.key(),
+        total_player: game_state.total_player,
+        prize_pool: game_state.prize_pool,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(RoundStarted {
+        game_id,
+        round_number: 1,
+        round_type: round_result.round_type,
+        start_price_btc: start_btc_price,
+        start_price_sol: start_sol_price,
+        round_start_ts: round_result.round_start_ts,
+        round_end_ts: round_result.round_end_ts,
+        magnitude_buckets: round_result.magnitude_buckets,
+        range_zones: round_result.range_zones,
+    });
     
     Ok(())
 }
\ No newline at end of file
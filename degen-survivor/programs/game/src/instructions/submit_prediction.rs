@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::GameError;
+use crate::events::PredictionSubmitted;
 use crate::state::*;
 
 
@@ -36,15 +37,57 @@ pub struct SumbitPredection <'info> {
 }
 
 pub fn handler(ctx:Context<SumbitPredection>,_game_id:u64,round_number:u8,choice : PredectionChoice)->Result<()>{
-    let game_state = &mut ctx.accounts.game_state;
+    let game_state = &ctx.accounts.game_state;
     let player_state = &mut ctx.accounts.player_state;
-    let round_result = &mut ctx.accounts.round_result;
+    let round_result = &ctx.accounts.round_result;
     let clock = Clock::get()?;
+
+    // commit-reveal games never see a plaintext choice during the round
+    require!(!game_state.commit_reveal, GameError::CommitRevealRequired);
+
+    let response_time = record_prediction(
+        game_state,
+        player_state,
+        round_result,
+        round_number,
+        Some(choice),
+        None,
+        clock.unix_timestamp,
+    )?;
+
+    msg!("Prediction submitted!");
+    msg!("Player: {}", ctx.accounts.player.key());
+    msg!("Round: {}", round_number);
+    msg!("Choice: {:?}", choice);
+    msg!("Response time: {}ms", response_time * 1000);
+
+    emit!(PredictionSubmitted {
+        game_id: game_state.game_id,
+        player: ctx.accounts.player.key(),
+        round_number,
+        choice,
+        response_time,
+        submitted_at: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Check the prediction window and store a plain or committed prediction.
+/// Returns the response time, measured from round start to submission.
+pub fn record_prediction(
+    game_state: &GameState,
+    player_state: &mut PlayerState,
+    round_result: &RoundResult,
+    round_number: u8,
+    choice: Option<PredectionChoice>,
+    commitment: Option<[u8; 32]>,
+    now: i64,
+) -> Result<u32> {
     // validate game is active 
     require!(game_state.status == GameStatus::Active, GameError::InvalidGameStatus);
 
     // validate round number 
-    require!(round_number >=1 && round_number<=5, GameError::InvalidRoundNumber);
+    require!(round_number >=1 && round_number<=game_state.total_round, GameError::InvalidRoundNumber);
     
     require!(
         round_number == game_state.current_round,
@@ -53,25 +96,37 @@ pub fn handler(ctx:Context<SumbitPredection>,_game_id:u64,round_number:u8,choice
     // check if player is already predicted
     
     require!(!player_state.has_predicted(round_number),GameError::AlreadyPredicted);
+
+    // Survivor: knocked out players sit the rest of the game out
+    require!(!player_state.is_eliminated(), GameError::PlayerEliminated);
+
+    // plain choices must fit the round, committed ones are checked at reveal
+    if let Some(choice) = choice {
+        require!(
+            round_result.round_type.allows(choice),
+            GameError::InvalidPredictionChoice
+        );
+    }
     // check predection window is open 
     require!(
-        clock.unix_timestamp < round_result.round_end_ts,
+        now < round_result.round_end_ts,
         GameError::PredictionWindowClosed
     );
     let lockout_start = round_result.round_end_ts
-    .checked_sub(PREDICTION_LOCKOUT_SECONDS)
+    .checked_sub(game_state.prediction_lockout_seconds)
     .ok_or(GameError::ArithmeticOverflow)?;
 
     require!(
-        clock.unix_timestamp < lockout_start,
+        now < lockout_start,
         GameError::PredictionTooLate
     );
 
-    let response_time  = clock.unix_timestamp.checked_sub(round_result.round_start_ts).ok_or(GameError::ArithmeticOverflow)? as u32;
+    let response_time  = now.checked_sub(round_result.round_start_ts).ok_or(GameError::ArithmeticOverflow)? as u32;
     let predection = RoundPrediction{
         round : round_number,
         choice,
-        sumbited_at : clock.unix_timestamp,
+        commitment,
+        sumbited_at : now,
         response_time,
         point_earned : 0 ,// filled during evualaltion time 
         is_correct : false
@@ -86,13 +141,8 @@ pub fn handler(ctx:Context<SumbitPredection>,_game_id:u64,round_number:u8,choice
 
     //  if tie braker then chekcs for first round question 
     if round_number==1{
-        player_state.first_prediction_ts = clock.unix_timestamp;
+        player_state.first_prediction_ts = now;
     }
 
-    msg!("Prediction submitted!");
-    msg!("Player: {}", ctx.accounts.player.key());
-    msg!("Round: {}", round_number);
-    msg!("Choice: {:?}", choice);
-    msg!("Response time: {}ms", response_time * 1000);
-    Ok(())
-}
\ No newline at end of file
+    Ok(response_time)
+}
@@ -0,0 +1,302 @@
+use anchor_lang::prelude::*;
+use vault::constants::GLOBAL_VAULT_SEED;
+use vault::state::GlobalVault;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::TemplateUpdated;
+use crate::state::*;
+
+/// Create a new game template
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateTemplate<'info> {
+    /// Platform admin (GlobalVault admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// GlobalVault PDA, source of the platform admin
+    #[account(
+        seeds = [GLOBAL_VAULT_SEED],
+        seeds::program = vault::ID,
+        bump = global_vault.bump,
+        constraint = global_vault.admin == admin.key() @ GameError::Unauthorized,
+    )]
+    pub global_vault: Account<'info, GlobalVault>,
+
+    /// GameTemplate account
+    #[account(
+        init,
+        payer = admin,
+        space = GameTemplate::SIZE,
+        seeds = [TEMPLATE_SEED, template_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub template: Account<'info, GameTemplate>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_template(
+    ctx: Context<CreateTemplate>,
+    template_id: u64,
+    config: TemplateConfig,
+) -> Result<()> {
+    validate_template_config(&config)?;
+
+    let template = &mut ctx.accounts.template;
+    template.template_id = template_id;
+    template.authority = ctx.accounts.admin.key();
+    template.active = true;
+    template.bump = ctx.bumps.template;
+
+    msg!("Game template created!");
+    msg!("Template ID: {}", template_id);
+    msg!("Rounds: {}", config.total_rounds);
+    msg!("Round duration: {}", config.round_duration_seconds);
+    msg!("Players: {} - {}", config.min_player, config.max_player);
+
+    template.config = config;
+
+    emit_template_updated(template);
+
+    Ok(())
+}
+
+/// Update an existing game template, games already created keep their copy
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct UpdateTemplate<'info> {
+    /// Platform admin (GlobalVault admin)
+    pub admin: Signer<'info>,
+
+    /// GlobalVault PDA, source of the platform admin
+    #[account(
+        seeds = [GLOBAL_VAULT_SEED],
+        seeds::program = vault::ID,
+        bump = global_vault.bump,
+        constraint = global_vault.admin == admin.key() @ GameError::Unauthorized,
+    )]
+    pub global_vault: Account<'info, GlobalVault>,
+
+    /// GameTemplate account
+    #[account(
+        mut,
+        seeds = [TEMPLATE_SEED, template_id.to_le_bytes().as_ref()],
+        bump = template.bump,
+    )]
+    pub template: Account<'info, GameTemplate>,
+}
+
+pub fn update_template(
+    ctx: Context<UpdateTemplate>,
+    template_id: u64,
+    config: TemplateConfig,
+    active: bool,
+) -> Result<()> {
+    validate_template_config(&config)?;
+
+    let template = &mut ctx.accounts.template;
+    template.config = config;
+    template.active = active;
+
+    msg!("Game template updated!");
+    msg!("Template ID: {}", template_id);
+    msg!("Active: {}", active);
+
+    emit_template_updated(template);
+
+    Ok(())
+}
+
+fn emit_template_updated(template: &GameTemplate) {
+    emit!(TemplateUpdated {
+        template_id: template.template_id,
+        authority: template.authority,
+        active: template.active,
+        total_rounds: template.config.total_rounds,
+        round_duration_seconds: template.config.round_duration_seconds,
+        max_player: template.config.max_player,
+        min_player: template.config.min_player,
+        platform_fee_bps: template.config.platform_fee_bps,
+    });
+}
+
+/// Reject configurations a game could not be played with
+pub fn validate_template_config(config: &TemplateConfig) -> Result<()> {
+    require!(
+        config.total_rounds >= 1 && config.total_rounds <= MAX_ROUNDS
+            && config.round_types.len() == config.total_rounds as usize,
+        GameError::InvalidTemplateConfig
+    );
+
+    // lockout has to leave part of the round open for predictions
+    require!(
+        config.prediction_lockout_seconds >= 0
+            && config.round_duration_seconds > config.prediction_lockout_seconds,
+        GameError::InvalidTemplateConfig
+    );
+
+    // rounds must not overlap
+    require!(
+        config.round_gap_seconds >= config.round_duration_seconds,
+        GameError::InvalidTemplateConfig
+    );
+
+    require!(
+        config.min_player >= MIN_PLAYER && config.max_player >= config.min_player,
+        GameError::InvalidTemplateConfig
+    );
+
+    require!(
+        config.platform_fee_bps <= MAX_PLATFORM_FEE_BPS,
+        GameError::InvalidTemplateConfig
+    );
+
+    // Range rounds need zones with some width
+    let has_range_round = config.round_types.contains(&RoundType::Range);
+    require!(
+        config.range_zone_width_bps <= MAX_RANGE_ZONE_WIDTH_BPS
+            && (!has_range_round || config.range_zone_width_bps > 0),
+        GameError::InvalidTemplateConfig
+    );
+
+    // Magnitude rounds need strictly ascending bucket bounds for both assets
+    if config.round_types.contains(&RoundType::Magnitude) {
+        require!(
+            ascending_buckets(&config.btc_magnitude_buckets)
+                && ascending_buckets(&config.sol_magnitude_buckets),
+            GameError::InvalidTemplateConfig
+        );
+    }
+
+    Ok(())
+}
+
+/// Bucket bounds must leave every bucket, RangeA included, a non-empty span
+fn ascending_buckets(buckets: &[u64; 3]) -> bool {
+    buckets[0] > 0 && buckets[0] < buckets[1] && buckets[1] < buckets[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(round_types: Vec<RoundType>) -> TemplateConfig {
+        TemplateConfig {
+            total_rounds: round_types.len() as u8,
+            round_duration_seconds: 60,
+            round_gap_seconds: 120,
+            prediction_lockout_seconds: 5,
+            max_player: 50,
+            min_player: 2,
+            platform_fee_bps: 600,
+            range_zone_width_bps: 10,
+            btc_magnitude_buckets: [50_000_000, 100_000_000, 200_000_000],
+            sol_magnitude_buckets: [100_000, 250_000, 500_000],
+            round_types,
+        }
+    }
+
+    #[test]
+    fn round_count_must_match_round_types() {
+        assert!(validate_template_config(&config(vec![RoundType::PriceDirection])).is_ok());
+        assert!(validate_template_config(&config(vec![RoundType::PriceDirection; MAX_ROUNDS as usize])).is_ok());
+        assert!(validate_template_config(&config(vec![])).is_err());
+        assert!(validate_template_config(&config(vec![RoundType::PriceDirection; MAX_ROUNDS as usize + 1])).is_err());
+
+        let mut mismatched = config(vec![RoundType::PriceDirection; 3]);
+        mismatched.total_rounds = 2;
+        assert!(validate_template_config(&mismatched).is_err());
+    }
+
+    #[test]
+    fn lockout_must_leave_the_round_open() {
+        let mut lockout = config(vec![RoundType::PriceDirection]);
+        lockout.prediction_lockout_seconds = 59;
+        assert!(validate_template_config(&lockout).is_ok());
+
+        lockout.prediction_lockout_seconds = 60;
+        assert!(validate_template_config(&lockout).is_err());
+
+        lockout.prediction_lockout_seconds = -1;
+        assert!(validate_template_config(&lockout).is_err());
+    }
+
+    #[test]
+    fn rounds_must_not_overlap() {
+        let mut gap = config(vec![RoundType::PriceDirection]);
+        gap.round_gap_seconds = 60;
+        assert!(validate_template_config(&gap).is_ok());
+
+        gap.round_gap_seconds = 59;
+        assert!(validate_template_config(&gap).is_err());
+    }
+
+    #[test]
+    fn player_limits_must_be_ordered() {
+        let mut players = config(vec![RoundType::PriceDirection]);
+        players.min_player = MIN_PLAYER - 1;
+        assert!(validate_template_config(&players).is_err());
+
+        players.min_player = 10;
+        players.max_player = 9;
+        assert!(validate_template_config(&players).is_err());
+
+        players.max_player = 10;
+        assert!(validate_template_config(&players).is_ok());
+    }
+
+    #[test]
+    fn platform_fee_is_capped() {
+        let mut fee = config(vec![RoundType::PriceDirection]);
+        fee.platform_fee_bps = MAX_PLATFORM_FEE_BPS;
+        assert!(validate_template_config(&fee).is_ok());
+
+        fee.platform_fee_bps = MAX_PLATFORM_FEE_BPS + 1;
+        assert!(validate_template_config(&fee).is_err());
+    }
+
+    #[test]
+    fn range_rounds_need_a_capped_zone_width() {
+        let mut range = config(vec![RoundType::Range]);
+        range.range_zone_width_bps = MAX_RANGE_ZONE_WIDTH_BPS;
+        assert!(validate_template_config(&range).is_ok());
+
+        range.range_zone_width_bps = MAX_RANGE_ZONE_WIDTH_BPS + 1;
+        assert!(validate_template_config(&range).is_err());
+
+        range.range_zone_width_bps = 0;
+        assert!(validate_template_config(&range).is_err());
+
+        let mut no_range = config(vec![RoundType::PriceDirection]);
+        no_range.range_zone_width_bps = 0;
+        assert!(validate_template_config(&no_range).is_ok());
+    }
+
+    #[test]
+    fn magnitude_buckets_must_ascend() {
+        assert!(validate_template_config(&config(vec![RoundType::Magnitude])).is_ok());
+
+        let mut flat = config(vec![RoundType::Magnitude]);
+        flat.btc_magnitude_buckets = [10, 10, 20];
+        assert!(validate_template_config(&flat).is_err());
+
+        let mut descending = config(vec![RoundType::Magnitude]);
+        descending.sol_magnitude_buckets = [30, 20, 10];
+        assert!(validate_template_config(&descending).is_err());
+
+        let mut empty_first = config(vec![RoundType::Magnitude]);
+        empty_first.btc_magnitude_buckets = [0, 10, 20];
+        assert!(validate_template_config(&empty_first).is_err());
+    }
+
+    #[test]
+    fn magnitude_buckets_are_ignored_without_magnitude_rounds() {
+        let mut no_magnitude = config(vec![RoundType::PriceDirection]);
+        no_magnitude.btc_magnitude_buckets = [0; 3];
+        no_magnitude.sol_magnitude_buckets = [0; 3];
+        assert!(validate_template_config(&no_magnitude).is_ok());
+    }
+}
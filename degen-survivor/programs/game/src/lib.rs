@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 
+pub mod allowlist;
 pub mod constants;
+pub mod crank;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod treasury;
 
 use instructions::*;
 use state::*;
@@ -22,29 +26,37 @@ pub mod game {
         game_type: GameType,
         start_time: i64,
         entry_fee: u64,
+        crank_reward: u64,
+        options: GameOptions,
     ) -> Result<()> {
-        instructions::create_game::handler(ctx, game_id, game_type, start_time, entry_fee)
+        instructions::create_game::handler(ctx, game_id, game_type, start_time, entry_fee, crank_reward, options)
     }
 
     /// Player joins an upcoming game
     /// Burns entry fee and creates PlayerState account
+    /// Private games need an allowlist proof and/or a co-signature from the access key
     pub fn join_game(
         ctx: Context<JoinGame>,
         game_id: u64,
         username: String,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        instructions::join_game::handler(ctx, game_id, username)
+        instructions::join_game::handler(ctx, game_id, username, proof)
     }
 
-    /// Start the game (called by backend at exactly start_time)
-    /// Changes status to Active and initializes Round 1
+    /// Player leaves a game before registration closes
+    /// Refunds the entry fee and closes PlayerState
+    pub fn leave_game(ctx: Context<LeaveGame>, game_id: u64) -> Result<()> {
+        instructions::leave_game::handler(ctx, game_id)
+    }
+
+    /// Start the game (permissionless once start_time has passed)
+    /// Changes status to Active and initializes Round 1 from oracle START snapshots
     pub fn start_game(
         ctx: Context<StartGame>,
         game_id: u64,
-        start_price_btc: Option<u64>,
-        start_price_sol: Option<u64>,
     ) -> Result<()> {
-        instructions::start_game::handler(ctx, start_price_btc, start_price_sol,game_id)
+        instructions::start_game::handler(ctx, game_id)
     }
 
     /// Player submits prediction for current round
@@ -58,8 +70,31 @@ pub mod game {
         instructions::submit_prediction::handler(ctx, game_id, round_number, choice)
     }
 
+    /// Commit hash(choice || salt || player || game_id || round_number) for the current round in a commit-reveal game
+    /// Response time is measured at commit
+    pub fn commit_prediction(
+        ctx: Context<CommitPrediction>,
+        game_id: u64,
+        round_number: u8,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_prediction::handler(ctx, game_id, round_number, commitment)
+    }
+
+    /// Reveal a committed prediction after the round ended
+    /// Commitments left unrevealed are scored as wrong
+    pub fn reveal_prediction(
+        ctx: Context<RevealPrediction>,
+        game_id: u64,
+        round_number: u8,
+        choice: PredectionChoice,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_prediction::handler(ctx, game_id, round_number, choice, salt)
+    }
+
     /// Evaluate a player's prediction for a completed round
-    /// Called by backend after round ends, calculates points
+    /// Permissionless once the round is resolved, calculates points
     pub fn evaluate_round(
         ctx: Context<EvualatedRound>,
         game_id: u64,
@@ -68,49 +103,43 @@ pub mod game {
         instructions::evaluate_round::handler(ctx, game_id, round_number)
     }
 
+    /// Evaluate many players for a completed round in one transaction
+    /// PlayerState accounts are passed through remaining_accounts
+    pub fn evaluate_round_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EvaluateRoundBatch<'info>>,
+        game_id: u64,
+        round_number: u8,
+    ) -> Result<()> {
+        instructions::evaluate_round_batch::handler(ctx, game_id, round_number)
+    }
+
     /// Advance to next round
-    /// Called by backend after all players evaluated for current round
+    /// Creator can call once the current round ended, anyone after the scheduled start
     pub fn advance_round(
         ctx: Context<AdvanceRound>,
         game_id: u64,
         next_round: u8,
-        start_price_btc: Option<u64>,
-        start_price_sol: Option<u64>,
     ) -> Result<()> {
-        instructions::advance_round::handler(ctx, game_id, next_round, start_price_btc, start_price_sol)
+        instructions::advance_round::handler(ctx, game_id, next_round)
     }
 
-    /// Set rank and prize amount for a player
-    /// Called by backend for each player after all rounds complete
-    pub fn finalize_leaderboard(
-        ctx: Context<FinalizedLeaderboard>,
+    /// Rank players and set prize amounts after the game is completed
+    /// Ranks come from the leaderboard built during evaluation, PlayerStates are passed in any order, each followed by its PlayerProfile
+    pub fn finalize_leaderboard<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizedLeaderboard<'info>>,
         game_id: u64,
-        rank: u16,
     ) -> Result<()> {
-        instructions::finalize_leaderboard::handler(ctx, game_id, rank)
+        instructions::finalize_leaderboard::handler(ctx, game_id)
     }
     
     /// Complete the game (mark as Completed status)
-    /// Called by backend after all players ranked
-    pub fn complete_game(ctx: Context<CompleteGame>, _game_id: u64) -> Result<()> {
-        let game_state = &mut ctx.accounts.game_state;
-        let clock = Clock::get()?;
-        
-        game_state.status = GameStatus::Completed;
-        game_state.leaderboard_finalized = true;
-        game_state.end_time = Some(clock.unix_timestamp);
-        
-        msg!("Game completed!");
-        msg!("Final players: {}", game_state.total_player);
-        msg!("Prize pool: {}", game_state.prize_pool);
-        msg!("Top scorer: {:?}", game_state.top_scorer);
-        msg!("Highest score: {}", game_state.higest_score);
-        
-        Ok(())
+    /// Creator can call once the last round is resolved, anyone after the grace period
+    pub fn complete_game(ctx: Context<CompleteGame>, game_id: u64) -> Result<()> {
+        instructions::complete_game::handler(ctx, game_id)
     }
 
     /// Player claims their prize
-    /// Can only be called by winners (rank 1-10)
+    /// Can only be called by winners, pays the prize_amount set at finalize_leaderboard from the pool
     pub fn claim_prize(
         ctx: Context<ClaimPrize>,
         game_id: u64,
@@ -118,66 +147,86 @@ pub mod game {
         instructions::claim_prize::handler(ctx, game_id)
     }
     
-    /// Update round result with end prices and correct answer
-    /// Called by backend after round ends (before evaluation)
-    pub fn update_round_result(
-        ctx: Context<UpdateRoundResult>,
-        _game_id: u64,
-        _round_number: u8,
-        end_price_btc: Option<u64>,
-        end_price_sol: Option<u64>,
-        correct_answer: PredectionChoice,
+    /// Resolve a round from the oracle START/END price snapshots
+    /// Permissionless: correct answer and price changes are derived on-chain
+    pub fn resolve_round(
+        ctx: Context<ResolveRound>,
+        game_id: u64,
+        round_number: u8,
     ) -> Result<()> {
-        let round_result = &mut ctx.accounts.round_result;
-        let clock = Clock::get()?;
-        
-        round_result.end_price_btc = end_price_btc;
-        round_result.end_price_sol = end_price_sol;
-        round_result.correct_answer = Some(correct_answer);
-        round_result.evaluation_ts = Some(clock.unix_timestamp);
-        
-        // Calculate price changes
-        if let (Some(start_btc), Some(end_btc)) = (round_result.start_price_btc, end_price_btc) {
-            round_result.price_change_btc = (end_btc as i64) - (start_btc as i64);
-        }
-        
-        if let (Some(start_sol), Some(end_sol)) = (round_result.start_price_sol, end_price_sol) {
-            round_result.price_change_sol = (end_sol as i64) - (start_sol as i64);
-        }
-        
-        msg!("Round result updated!");
-        msg!("End price BTC: {:?}", end_price_btc);
-        msg!("End price SOL: {:?}", end_price_sol);
-        msg!("Correct answer: {:?}", correct_answer);
-        
-        Ok(())
-    }
-}
-
-// Additional account contexts
-
-#[derive(Accounts)]
-#[instruction(game_id: u64)]
-pub struct CompleteGame<'info> {
-    pub creator: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"game", game_id.to_le_bytes().as_ref()],
-        bump = game_state.bump,
-    )]
-    pub game_state: Account<'info, GameState>,
-}
-
-#[derive(Accounts)]
-#[instruction(game_id: u64, round_number: u8)]
-pub struct UpdateRoundResult<'info> {
-    pub creator: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"round-result", game_id.to_le_bytes().as_ref(), &[round_number]],
-        bump = round_result.bump,
-    )]
-    pub round_result: Account<'info, RoundResult>,
+        instructions::resolve_round::handler(ctx, game_id, round_number)
+    }
+
+    /// Cancel a game that has not started or got stuck while active
+    /// Creator can cancel any time before start, anyone once it failed to fill or a transition is overdue by STUCK_GAME_TIMEOUT_SECONDS
+    pub fn cancel_game(ctx: Context<CancelGame>, game_id: u64) -> Result<()> {
+        instructions::cancel_game::handler(ctx, game_id)
+    }
+
+    /// Player reclaims their entry fee from a cancelled game
+    pub fn claim_refund(ctx: Context<ClaimRefund>, game_id: u64) -> Result<()> {
+        instructions::claim_refund::handler(ctx, game_id)
+    }
+
+    /// Add DEGEN to a game's prize pool
+    /// Any wallet can sponsor, the amount is recorded per sponsor
+    pub fn sponsor_pool(ctx: Context<SponsorPool>, game_id: u64, amount: u64) -> Result<()> {
+        instructions::sponsor::sponsor_pool(ctx, game_id, amount)
+    }
+
+    /// Set a guaranteed prize for a promoted game (platform admin only)
+    /// Treasury covers the shortfall at start_game
+    pub fn set_guaranteed_prize(ctx: Context<SetGuaranteedPrize>, game_id: u64, amount: u64) -> Result<()> {
+        instructions::sponsor::set_guaranteed_prize(ctx, game_id, amount)
+    }
+
+    /// Sponsor reclaims their top-up from a cancelled game
+    pub fn claim_sponsor_refund(ctx: Context<ClaimSponsorRefund>, game_id: u64) -> Result<()> {
+        instructions::sponsor::claim_sponsor_refund(ctx, game_id)
+    }
+
+    /// Close a PlayerState of a settled game
+    /// Permissionless, rent goes back to the player
+    pub fn close_player_state(ctx: Context<ClosePlayerState>, game_id: u64) -> Result<()> {
+        instructions::close::close_player_state(ctx, game_id)
+    }
+
+    /// Close a RoundResult of a settled game
+    /// Permissionless, rent goes back to whoever opened the round
+    pub fn close_round_result(ctx: Context<CloseRoundResult>, game_id: u64, round_number: u8) -> Result<()> {
+        instructions::close::close_round_result(ctx, game_id, round_number)
+    }
+
+    /// Close a Sponsorship of a settled game
+    /// Permissionless, rent goes back to the sponsor
+    pub fn close_sponsorship(ctx: Context<CloseSponsorship>, game_id: u64) -> Result<()> {
+        instructions::close::close_sponsorship(ctx, game_id)
+    }
+
+    /// Close a settled game, its pool token account and GameState
+    /// Requires every player, round and sponsorship account closed and its price snapshots expired
+    pub fn close_game(ctx: Context<CloseGame>, game_id: u64) -> Result<()> {
+        instructions::close::close_game(ctx, game_id)
+    }
+
+    /// Create a game template (rounds, timing, player limits, fee)
+    /// Admin only, create_game copies the template into the GameState
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        template_id: u64,
+        config: TemplateConfig,
+    ) -> Result<()> {
+        instructions::template::create_template(ctx, template_id, config)
+    }
+
+    /// Update or disable a game template
+    /// Admin only, existing games keep the values they were created with
+    pub fn update_template(
+        ctx: Context<UpdateTemplate>,
+        template_id: u64,
+        config: TemplateConfig,
+        active: bool,
+    ) -> Result<()> {
+        instructions::template::update_template(ctx, template_id, config, active)
+    }
 }
\ No newline at end of file
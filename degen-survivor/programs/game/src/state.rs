@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{BPS_DIVISOR, LEADERBOARD_SIZE, MAX_ROUNDS, STREAK_TIERS};
+use crate::errors::GameError;
+
 #[account]
 
 pub struct GameState{
@@ -25,8 +28,8 @@ pub struct GameState{
     pub current_round : u8,
 
     pub total_round : u8 ,
-    /// Deadlines for each round [Round1_end, Round2_end, ...]
-    pub round_deadline : [i64;5],
+    /// Deadlines for each round [Round1_end, Round2_end, ...], one per round
+    pub round_deadline : Vec<i64>,
     // per player 500 degen token 
     pub entry_fee : u64,
 
@@ -44,7 +47,7 @@ pub struct GameState{
     // to check for maximum player is reached 
     pub player_finalized : bool,    
     // types of question for each round 
-    pub round_types  : [RoundType;5],
+    pub round_types  : Vec<RoundType>,
 
     pub leaderboard_finalized : bool,
 
@@ -54,12 +57,297 @@ pub struct GameState{
 
     pub bump : u8,
 
-    pub _reserved : [u8;128],
+    /// Reward paid from the pool to third parties cranking lifecycle transitions
+    pub crank_reward : u64,
+
+    /// Players ranked so far by finalize_leaderboard
+    pub ranked_players : u16,
+
+    /// Players scored for every round, finalize_leaderboard waits for all of them
+    pub completed_players : u16,
+
+    /// Template the game was created from
+    pub template : Pubkey,
+
+    // timing and player limits copied from the template at create_game
+    pub round_duration_seconds : i64,
+    pub round_gap_seconds : i64,
+    pub prediction_lockout_seconds : i64,
+    pub min_player : u16,
+
+    /// Players commit hash(choice || salt || player || game_id || round) and reveal after the round ends
+    pub commit_reveal : bool,
+
+    // Classic plays every round, Survivor eliminates on a bad round
+    pub game_mode : GameMode,
+    // Survivor: a round scoring fewer points than this eliminates the player
+    pub elimination_threshold : u16,
+
+    // Survivor: survival level of the winners and how many share the pool
+    pub winning_round : u8,
+    pub winner_count : u16,
+
+    // Survivor: lives each player starts with, a bad round costs one
+    pub starting_lives : u8,
+
+    // optional bonus for answering early in the round
+    pub speed_bonus : Option<SpeedBonus>,
+
+    // optional multipliers (bps) for 1, 2, 3 and 4+ correct rounds in a row
+    pub streak_multipliers : Option<[u16; STREAK_TIERS]>,
+
+    // Range rounds: zone width around the start price in bps
+    pub range_zone_width_bps : u16,
+
+    // Magnitude rounds: RangeA..RangeC upper bounds on the absolute move per asset (micro-dollars)
+    pub btc_magnitude_buckets : [u64; 3],
+    pub sol_magnitude_buckets : [u64; 3],
+
+    // private games: merkle root of allowed wallets, key that must co-sign joins
+    pub allowlist_root : Option<[u8;32]>,
+    pub access_key : Option<Pubkey>,
+
+    // promoted games: pool the treasury tops up to at start_game
+    pub guaranteed_prize : u64,
+    // sponsor top-ups and treasury cover included in prize_pool
+    pub sponsored_amount : u64,
+    pub treasury_cover : u64,
+
+    /// Mint entry fees and prizes are paid in
+    pub token_mint : Pubkey,
+
+    // accounts opened and closed, close_game waits for all of them
+    pub sponsor_count : u16,
+    pub closed_players : u16,
+    pub closed_sponsors : u16,
+    pub closed_rounds : u8,
+
+    // crank rewards taken from the pool, part of what refunds are owed
+    pub crank_paid : u64,
+    // cancelled games: share of each entry and sponsorship refunded (bps)
+    pub refund_bps : u16,
+
+    // joins so far, never decremented by leave_game, hands out entry_slot
+    pub join_count : u64,
+
+    pub _reserved : [u8;2],
+
+    /// Best players so far, sorted, one entry per prize place
+    pub leaderboard : Vec<LeaderboardEntry>,
 }
 
 impl GameState {
-    // 8 (discriminator) + 8 + 1 + 32 + 1 + 8 + 8 + 9 + 9 + 1 + 1 + 40 + 8 + 8 + 2 + 1 + 2 + 2 + 1 + 5 + 1 + 33 + 2 + 1 + 128
-    pub const SIZE: usize = 8 + 8 + 1 + 32 + 1 + 8 + 8 + 9 + 9 + 1 + 1 + 40 + 8 + 8 + 2 + 1 + 2 + 2 + 1 + 5 + 1 + 33 + 2 + 1 + 128;
+    // 8 (discriminator) + 8 + 1 + 32 + 1 + 8 + 8 + 9 + 9 + 1 + 1 + 8 + 8 + 32 + 2 + 1 + 2 + 2 + 1 + 1 + 33 + 2 + 1 + 8 + 2 + 2 + 32 + 8 + 8 + 8 + 2 + 1 + 1 + 2 + 1 + 2 + 1 + 8 + 9 + 2 + 24 + 24 + 33 + 33 + 8 + 8 + 8 + 32 + 2 + 2 + 2 + 1 + 8 + 2 + 8 + 2
+    // without the round_deadline, round_types and leaderboard vectors
+    pub const BASE_SIZE: usize = 8 + 8 + 1 + 32 + 1 + 8 + 8 + 9 + 9 + 1 + 1 + 8 + 8 + 32 + 2 + 1 + 2 + 2 + 1 + 1 + 33 + 2 + 1 + 8 + 2 + 2 + 32 + 8 + 8 + 8 + 2 + 1 + 1 + 2 + 1 + 2 + 1 + 8 + 9 + 2 + 24 + 24 + 33 + 33 + 8 + 8 + 8 + 32 + 2 + 2 + 2 + 1 + 8 + 2 + 8 + 2;
+
+    /// Account size for a game with `total_rounds` rounds
+    pub fn space(total_rounds: u8) -> usize {
+        let rounds = total_rounds as usize;
+        // vec prefix + deadline (i64) and round type (u8) per round,
+        // then the full leaderboard
+        Self::BASE_SIZE + (4 + 8 * rounds) + (4 + rounds) + (4 + LeaderboardEntry::SIZE * LEADERBOARD_SIZE)
+    }
+
+    /// Count a player who finished every round and insert them into the
+    /// leaderboard if they reach a prize place. Survivor games also track the
+    /// best survival level and how many players reached it.
+    pub fn record_completed(&mut self, player: Pubkey, key: RankKey) -> Result<()> {
+        self.completed_players = self.completed_players
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+
+        if self.game_mode == GameMode::Survivor {
+            if key.survived > self.winning_round {
+                self.winning_round = key.survived;
+                self.winner_count = 1;
+            } else if key.survived == self.winning_round {
+                self.winner_count = self.winner_count
+                    .checked_add(1)
+                    .ok_or(GameError::ArithmeticOverflow)?;
+            }
+        }
+
+        let position = self.leaderboard
+            .iter()
+            .position(|entry| key.ranks_before(&entry.key))
+            .unwrap_or(self.leaderboard.len());
+        if position < LEADERBOARD_SIZE {
+            self.leaderboard.insert(position, LeaderboardEntry { player, key });
+            self.leaderboard.truncate(LEADERBOARD_SIZE);
+        }
+
+        if let Some(top) = self.leaderboard.first() {
+            self.top_scorer = Some(top.player);
+            self.higest_score = top.key.total_score;
+        }
+        Ok(())
+    }
+
+    /// Count a new player and their entry, returns their entry slot.
+    /// Slots come from join_count, which leave_game never takes back,
+    /// so a slot is never handed out twice.
+    pub fn record_join(&mut self, entry_paid: u64) -> Result<u64> {
+        self.join_count = self.join_count
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        self.total_player = self.total_player
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        self.prize_pool = self.prize_pool
+            .checked_add(entry_paid)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(self.join_count)
+    }
+
+    /// Take a leaving player and their refunded entry back out
+    pub fn record_leave(&mut self, refund: u64) -> Result<()> {
+        self.total_player = self.total_player
+            .checked_sub(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        self.prize_pool = self.prize_pool
+            .checked_sub(refund)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Part of `amount` paid back by a refund once the game is cancelled
+    pub fn refund_share(&self, amount: u64) -> Result<u64> {
+        let share = (amount as u128)
+            .checked_mul(self.refund_bps as u128)
+            .ok_or(GameError::ArithmeticOverflow)?
+            / BPS_DIVISOR as u128;
+        u64::try_from(share).map_err(|_| GameError::ArithmeticOverflow.into())
+    }
+
+    /// Leaderboard position of the player, None outside the prize places
+    pub fn leaderboard_rank(&self, player: &Pubkey) -> Option<u16> {
+        self.leaderboard
+            .iter()
+            .position(|entry| entry.player == *player)
+            .map(|index| index as u16 + 1)
+    }
+}
+
+/// Admin-created game configuration referenced by create_game
+#[account]
+
+pub struct GameTemplate {
+    pub template_id : u64,
+    // admin who created the template
+    pub authority : Pubkey,
+    // disabled templates cannot be used for new games
+    pub active : bool,
+
+    pub config : TemplateConfig,
+
+    pub bump : u8,
+
+    pub _reserved : [u8;64],
+}
+
+impl GameTemplate {
+    // 8 (discriminator) + 8 + 32 + 1 + TemplateConfig + 1 + 64
+    pub const SIZE: usize = 8 + 8 + 32 + 1 + TemplateConfig::MAX_SIZE + 1 + 64;
+}
+
+/// Per-game rule switches passed to create_game
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+
+pub struct GameOptions {
+    // players commit hash(choice || salt || player || game_id || round) and reveal after the round
+    pub commit_reveal : bool,
+
+    pub game_mode : GameMode,
+    // Survivor only, 0 for Classic
+    pub elimination_threshold : u16,
+    // Survivor only, 1 is sudden death, 0 for Classic
+    pub lives : u8,
+
+    pub speed_bonus : Option<SpeedBonus>,
+
+    pub streak_multipliers : Option<[u16; STREAK_TIERS]>,
+
+    // private games: only wallets in the merkle tree can join
+    pub allowlist_root : Option<[u8;32]>,
+    // private games: joiners must get this key to co-sign, the creator
+    // hands the keypair out off-chain so no secret is sent in the transaction
+    pub access_key : Option<Pubkey>,
+
+    // mint the game is played in, None for the vault's DEGEN mint
+    pub token_mint : Option<Pubkey>,
+}
+
+/// Extra points for early answers: up to max_bonus_bps of the points earned,
+/// shrinking over the prediction window along the decay curve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+
+pub struct SpeedBonus {
+    pub max_bonus_bps : u16,
+
+    pub decay : BonusDecay,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+
+pub enum BonusDecay {
+    // straight line down to zero at lockout
+    Linear,
+    // drops fast early on, rewards the very first seconds
+    Quadratic,
+    // halves every `seconds`
+    HalfLife { seconds : u32 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+
+pub struct TemplateConfig {
+    // number of rounds played (1-MAX_ROUNDS)
+    pub total_rounds : u8,
+    // how long a round accepts predictions
+    pub round_duration_seconds : i64,
+    // time between two round starts
+    pub round_gap_seconds : i64,
+    // last N seconds of a round where predictions are locked
+    pub prediction_lockout_seconds : i64,
+
+    pub max_player : u16,
+
+    pub min_player : u16,
+    // platform fees in bias point (600=6%)
+    pub platform_fee_bps : u16,
+    // Range rounds: zone width around the start price in bps (10 = 0.1%)
+    pub range_zone_width_bps : u16,
+    // Magnitude rounds: ascending RangeA..RangeC upper bounds on the absolute move (micro-dollars),
+    // a move on a bound belongs to the bucket above, anything from the last bound up is RangeD
+    pub btc_magnitude_buckets : [u64; 3],
+    pub sol_magnitude_buckets : [u64; 3],
+    // types of question for each round, one per round
+    pub round_types : Vec<RoundType>,
+}
+
+impl TemplateConfig {
+    // round_types sized for the longest game
+    pub const MAX_SIZE: usize = 1 + 8 + 8 + 8 + 2 + 2 + 2 + 2 + 24 + 24 + 4 + MAX_ROUNDS as usize;
+}
+
+/// DEGEN a wallet added to a game's pool, refundable if the game is cancelled
+#[account]
+
+pub struct Sponsorship {
+    pub game_id : u64,
+
+    pub sponsor : Pubkey,
+    // total sponsored by this wallet
+    pub amount : u64,
+
+    pub refunded : bool,
+
+    pub bump : u8,
+}
+
+impl Sponsorship {
+    pub const SIZE: usize = 8 + 8 + 32 + 8 + 1 + 1;
 }
 
 #[account]
@@ -73,9 +361,10 @@ pub struct PlayerState{
 
     pub entry_slot : u64,
 
-    pub predection : [Option<RoundPrediction>;5],
+    // one slot per round of the game
+    pub predection : Vec<Option<RoundPrediction>>,
 
-    pub scores : [u16;5],
+    pub scores : Vec<u16>,
 
     pub total_score : u16,
 
@@ -97,14 +386,127 @@ pub struct PlayerState{
     
     /// PDA bump
     pub bump: u8,
-    
+
+    /// Entry fee returned after the game was cancelled
+    pub refund_claimed: bool,
+
+    /// Survivor: round the player was knocked out in
+    pub eliminated_round: Option<u8>,
+
+    /// Survivor: lives left, eliminated when it reaches zero
+    pub lives_remaining: u8,
+
+    /// Exact predictions in a row, reset by any other result
+    pub streak: u8,
+
+    /// Longest streak reached in the game
+    pub best_streak: u8,
+
+    /// Entry fee that reached the pool (less than entry_fee with transfer fees)
+    pub entry_paid: u64,
+
+    /// Processed by finalize_leaderboard, final_rank stays None outside the prize places
+    pub ranked: bool,
+
     /// Reserved
-    pub _reserved: [u8; 64],
+    pub _reserved: [u8; 49],
 }
 
 impl PlayerState {
-    // Rough calculation - actual size will be larger due to Option and String
-    pub const SIZE: usize = 8 + 8 + 32 + 32 + 8 + 600 + 10 + 2 + 1 + 1 + 3 + 8 + 1 + 8 + 4 + 8 + 1 + 64;
+    // 8 (discriminator) + 8 + 32 + (4 + MAX_USERNAME_LENGTH rounded up) + 8 + 2 + 1 + 1 + 3 + 8 + 1 + 8 + 4 + 8 + 1 + 1 + 2 + 1 + 1 + 1 + 8 + 1 + 49
+    // without the predection and scores vectors
+    pub const BASE_SIZE: usize = 8 + 8 + 32 + 32 + 8 + 2 + 1 + 1 + 3 + 8 + 1 + 8 + 4 + 8 + 1 + 1 + 2 + 1 + 1 + 1 + 8 + 1 + 49;
+
+    /// Account size for a player in a game with `total_rounds` rounds
+    pub fn space(total_rounds: u8) -> usize {
+        let rounds = total_rounds as usize;
+        // vec prefix + optional prediction and score per round
+        Self::BASE_SIZE + (4 + (1 + RoundPrediction::SIZE) * rounds) + (4 + 2 * rounds)
+    }
+}
+
+/// Career stats of a wallet across every game it joined
+#[account]
+
+pub struct PlayerProfile {
+    pub player : Pubkey,
+
+    // counted at join_game and taken back at leave_game, cancelled games included
+    pub games_joined : u32,
+
+    // counted when the player is ranked at finalize_leaderboard
+    pub games_played : u32,
+
+    // Classic: ranked first, Survivor: among the last survivors
+    pub games_won : u32,
+
+    pub total_score : u64,
+
+    pub best_rank : Option<u16>,
+
+    pub prizes_claimed : u32,
+
+    pub total_winnings : u64,
+
+    pub created_at : i64,
+
+    pub last_joined_at : i64,
+
+    pub bump : u8,
+
+    /// Reserved
+    pub _reserved: [u8; 32],
+}
+
+impl PlayerProfile {
+    // 8 + 32 + 4 + 4 + 4 + 8 + 3 + 4 + 8 + 8 + 8 + 1 + 32
+    pub const SIZE: usize = 8 + 32 + 4 + 4 + 4 + 8 + 3 + 4 + 8 + 8 + 8 + 1 + 32;
+
+    /// Count a game the player joined
+    pub fn record_join(&mut self, now: i64) -> Result<()> {
+        self.games_joined = self.games_joined
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        self.last_joined_at = now;
+        Ok(())
+    }
+
+    /// Take back the join of a game the player left before it started
+    pub fn record_leave(&mut self) -> Result<()> {
+        self.games_joined = self.games_joined
+            .checked_sub(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Fold a finished game into the career stats
+    pub fn record_ranked(&mut self, rank: Option<u16>, total_score: u16, won: bool) -> Result<()> {
+        self.games_played = self.games_played
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        if won {
+            self.games_won = self.games_won
+                .checked_add(1)
+                .ok_or(GameError::ArithmeticOverflow)?;
+        }
+        self.total_score = self.total_score
+            .checked_add(total_score as u64)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        if let Some(rank) = rank {
+            self.best_rank = Some(self.best_rank.map_or(rank, |best| best.min(rank)));
+        }
+        Ok(())
+    }
+
+    pub fn record_prize(&mut self, amount: u64) -> Result<()> {
+        self.prizes_claimed = self.prizes_claimed
+            .checked_add(1)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        self.total_winnings = self.total_winnings
+            .checked_add(amount)
+            .ok_or(GameError::ArithmeticOverflow)?;
+        Ok(())
+    }
 }
 
 
@@ -113,7 +515,7 @@ impl PlayerState {
 pub struct RoundResult {
     // which game is belong to 
     pub game_id : u64 ,
-    // round number (1-5)
+    // round number (1-total_round)
     pub round_number : u8,
     //  Types of Question 
     pub round_type : RoundType,
@@ -144,17 +546,38 @@ pub struct RoundResult {
     pub wrong_predection : u16,
 
     pub bump : u8,   
+
+    // Survivor: players eliminated in this round
+    pub knocked_out : u16,
+
+    // Magnitude: upper bounds of RangeA, RangeB and RangeC (micro-dollars),
+    // fixed at round start for the asset the round is scored on
+    pub magnitude_buckets : Option<[u64; 3]>,
+
+    // Range: zone bounds [ZoneA/ZoneB, ZoneB/ZoneC, ZoneC/ZoneD] centered on
+    // the start price of the asset the round is scored on (micro-dollars)
+    pub range_zones : Option<[u64; 3]>,
+
+    // Trend: prices halfway through the round
+    pub mid_price_btc : Option<u64>,
+
+    pub mid_price_sol : Option<u64>,
+
+    // wallet that paid the rent, refunded on close
+    pub payer : Pubkey,
 }
 
 impl RoundResult {
-    pub const SIZE: usize = 8 + 8 + 1 + 1 + 9 + 9 + 9 + 9 + 8 + 8 + 2 + 8 + 8 + 9 + 2 + 2 + 2 + 2 + 1 + 32;
+    pub const SIZE: usize = 8 + 8 + 1 + 1 + 9 + 9 + 9 + 9 + 8 + 8 + 2 + 8 + 8 + 9 + 2 + 2 + 2 + 2 + 1 + 2 + 25 + 25 + 9 + 9 + 32 + 30;
 }
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 
 pub struct  RoundPrediction {
     pub round : u8,
-    // Player chois for evualation
-    pub choice : PredectionChoice,
+    // Player chois for evualation, None until a commitment is revealed
+    pub choice : Option<PredectionChoice>,
+    // hash(choice || salt || player || game_id || round) in commit-reveal games
+    pub commitment : Option<[u8;32]>,
     // when sumbited 
     pub sumbited_at : i64 ,
 
@@ -166,6 +589,61 @@ pub struct  RoundPrediction {
     pub is_correct : bool
 }
 
+impl RoundPrediction {
+    pub const SIZE: usize = 1 + 2 + 33 + 8 + 4 + 2 + 1;
+}
+
+/// Leaderboard sort key: survived longer, then higher score, then faster
+/// average response, then earlier first prediction, then earlier entry slot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+
+pub struct RankKey {
+    // elimination round, u8::MAX when never eliminated
+    pub survived : u8,
+    pub total_score : u16,
+    pub avg_response_time : u32,
+    pub first_prediction_ts : i64,
+    pub entry_slot : u64,
+}
+
+impl RankKey {
+    pub fn from_player(player_state: &PlayerState) -> Self {
+        // no round 1 prediction loses the timestamp tie-break
+        let first_prediction_ts = if player_state.first_prediction_ts == 0 {
+            i64::MAX
+        } else {
+            player_state.first_prediction_ts
+        };
+
+        RankKey {
+            survived: player_state.survival_level(),
+            total_score: player_state.total_score,
+            avg_response_time: player_state.avg_response_time,
+            first_prediction_ts,
+            entry_slot: player_state.entry_slot,
+        }
+    }
+
+    /// Strictly better position on the leaderboard than `other`
+    pub fn ranks_before(&self, other: &RankKey) -> bool {
+        (std::cmp::Reverse(self.survived), std::cmp::Reverse(self.total_score), self.avg_response_time, self.first_prediction_ts, self.entry_slot)
+            < (std::cmp::Reverse(other.survived), std::cmp::Reverse(other.total_score), other.avg_response_time, other.first_prediction_ts, other.entry_slot)
+    }
+}
+
+/// Player holding a prize place
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+
+pub struct LeaderboardEntry {
+    pub player : Pubkey,
+    pub key : RankKey,
+}
+
+impl LeaderboardEntry {
+    // 32 + RankKey (1 + 2 + 4 + 8 + 8)
+    pub const SIZE: usize = 32 + 1 + 2 + 4 + 8 + 8;
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 
 pub enum GameType {
@@ -174,6 +652,15 @@ pub enum GameType {
     BtcVsSol
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+
+pub enum GameMode {
+    // everyone plays every round, prizes by rank
+    Classic,
+    // a bad round eliminates, the last survivors split the pool
+    Survivor
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 
 pub enum  GameStatus {
@@ -198,6 +685,29 @@ pub enum  RoundType {
     Trend
 }
 
+impl RoundType {
+    /// Choices a player can pick in a round of this type
+    pub fn allows(&self, choice: PredectionChoice) -> bool {
+        use PredectionChoice::*;
+        match self {
+            RoundType::PriceDirection => matches!(choice, Up | Down),
+            RoundType::Magnitude => matches!(choice, RangeA | RangeB | RangeC | RangeD),
+            RoundType::Comperative => matches!(choice, BtcMore | SolMore | Equal),
+            RoundType::Range => matches!(choice, ZoneA | ZoneB | ZoneC | ZoneD),
+            RoundType::Trend => matches!(choice, HigherHigher | LowerLower | HigherLower | LowerHigher),
+        }
+    }
+
+    /// Whether rounds of this type can be played and resolved in a game type.
+    /// Comparing assets needs both of them.
+    pub fn supported_by(&self, game_type: GameType) -> bool {
+        match self {
+            RoundType::PriceDirection | RoundType::Magnitude | RoundType::Range | RoundType::Trend => true,
+            RoundType::Comperative => game_type == GameType::BtcVsSol,
+        }
+    }
+}
+
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 
@@ -209,10 +719,11 @@ pub enum PredectionChoice {
     // Magnitude 
 
     // for magnitude
-    RangeA, //less than $50
-    RangeB, // between 50 and 100
-    RangeC, //
-    RangeD,
+    // bucket bounds are stored on the RoundResult
+    RangeA, // below the first bound
+    RangeB, // between the first and second bound
+    RangeC, // between the second and third bound
+    RangeD, // third bound and above
 
     // for comperative 
 
@@ -222,17 +733,22 @@ pub enum PredectionChoice {
 
     // For Price Range 
 
-    ZoneA,
-    ZoneB,
-    ZoneC,
-    ZoneD,
+    // zone bounds are stored on the RoundResult, decided by the end price
+    ZoneA, // below the lower bound
+    ZoneB, // lower bound up to, not including, the start price
+    ZoneC, // start price up to, not including, the upper bound
+    ZoneD, // upper bound and above
 
-    // for trend 
+    // for trend: first half then second half of the round (start -> mid -> end)
 
     HigherHigher, 
     LowerLower,
     HigherLower,
     LowerHigher,
+
+    // PriceDirection answer when the price did not move, no player can pick it
+    // (appended last so commitments of the other choices keep their byte)
+    Flat,
 }
 
 
@@ -241,17 +757,299 @@ impl  PlayerState {
     //  prevent Double Predection 
     // so it checks predection for Specific round and make it true if not asnwered and false if no such round exist 
     pub fn has_predicted(&self,round:u8)->bool{
-        if round == 0 || round > 5 {
+        if round == 0 || round as usize > self.predection.len() {
             return false
         }else {
             self.predection[(round-1) as usize].is_some()
         }
     }
 
+    pub fn is_eliminated(&self) -> bool {
+        self.eliminated_round.is_some()
+    }
+
+    /// Higher is better: the elimination round, or u8::MAX for survivors
+    pub fn survival_level(&self) -> u8 {
+        self.eliminated_round.unwrap_or(u8::MAX)
+    }
+
+    /// Winner who has not claimed yet, same rules as claim_prize
+    pub fn has_unclaimed_prize(&self, game_state: &GameState) -> bool {
+        if self.prize_claimed || !self.ranked {
+            return false;
+        }
+
+        match game_state.game_mode {
+            GameMode::Classic => {
+                self.final_rank.is_some_and(|rank| rank as usize <= LEADERBOARD_SIZE) && self.prize_amount > 0
+            }
+            GameMode::Survivor => self.survival_level() == game_state.winning_round && self.prize_amount > 0,
+        }
+    }
+
     pub fn get_prediction(&self, round: u8) -> Option<&RoundPrediction> {
-        if round == 0 || round > 5 {
+        if round == 0 || round as usize > self.predection.len() {
             return None;
         }
         self.predection[(round - 1) as usize].as_ref()
     }
+}
+
+#[cfg(test)]
+impl GameState {
+    /// Pending 5 round Classic game nobody joined yet, for unit tests
+    pub fn test_game() -> Self {
+        let start_time = 10_000;
+        GameState {
+            game_id: 1,
+            game_type: GameType::BtcOnly,
+            creator: Pubkey::new_unique(),
+            status: GameStatus::Pending,
+            created_at: 0,
+            start_time,
+            actual_start_time: None,
+            end_time: None,
+            current_round: 0,
+            total_round: 5,
+            round_deadline: (0..5).map(|i| start_time + 120 * i + 60).collect(),
+            entry_fee: 100,
+            prize_pool: 0,
+            prize_pool_token_account: Pubkey::new_unique(),
+            platform_fee_bps: 600,
+            prize_pool_distributed: false,
+            total_player: 0,
+            max_player: 50,
+            player_finalized: false,
+            round_types: vec![RoundType::PriceDirection; 5],
+            leaderboard_finalized: false,
+            top_scorer: None,
+            higest_score: 0,
+            bump: 255,
+            crank_reward: 0,
+            ranked_players: 0,
+            completed_players: 0,
+            template: Pubkey::new_unique(),
+            round_duration_seconds: 60,
+            round_gap_seconds: 120,
+            prediction_lockout_seconds: 5,
+            min_player: 2,
+            commit_reveal: false,
+            game_mode: GameMode::Classic,
+            elimination_threshold: 0,
+            winning_round: 0,
+            winner_count: 0,
+            starting_lives: 1,
+            speed_bonus: None,
+            streak_multipliers: None,
+            range_zone_width_bps: 10,
+            btc_magnitude_buckets: [50_000_000, 100_000_000, 200_000_000],
+            sol_magnitude_buckets: [100_000, 250_000, 500_000],
+            allowlist_root: None,
+            access_key: None,
+            guaranteed_prize: 0,
+            sponsored_amount: 0,
+            treasury_cover: 0,
+            token_mint: Pubkey::new_unique(),
+            sponsor_count: 0,
+            closed_players: 0,
+            closed_sponsors: 0,
+            closed_rounds: 0,
+            crank_paid: 0,
+            refund_bps: 0,
+            join_count: 0,
+            _reserved: [0; 2],
+            leaderboard: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl PlayerState {
+    /// Player who joined a `total_round` round game and has not played yet, for unit tests
+    pub fn test_player(total_round: u8) -> Self {
+        PlayerState {
+            game_id: 1,
+            player: Pubkey::new_unique(),
+            username: String::from("player"),
+            entry_slot: 1,
+            predection: vec![None; total_round as usize],
+            scores: vec![0; total_round as usize],
+            total_score: 0,
+            round_evaluated: 0,
+            all_round_completed: false,
+            final_rank: None,
+            prize_amount: 0,
+            prize_claimed: false,
+            total_reponse_time: 0,
+            avg_response_time: 0,
+            first_prediction_ts: 0,
+            bump: 255,
+            refund_claimed: false,
+            eliminated_round: None,
+            lives_remaining: 1,
+            streak: 0,
+            best_streak: 0,
+            entry_paid: 100,
+            ranked: false,
+            _reserved: [0; 49],
+        }
+    }
+}
+
+#[cfg(test)]
+impl RoundResult {
+    /// Unresolved 60 second round starting at 0, for unit tests
+    pub fn test_round(round_type: RoundType) -> Self {
+        RoundResult {
+            game_id: 1,
+            round_number: 1,
+            round_type,
+            start_price_btc: None,
+            end_price_btc: None,
+            start_price_sol: None,
+            end_price_sol: None,
+            price_change_btc: 0,
+            price_change_sol: 0,
+            correct_answer: None,
+            round_start_ts: 0,
+            round_end_ts: 60,
+            evaluation_ts: None,
+            total_predection: 0,
+            correct_predection: 0,
+            partial_correct: 0,
+            wrong_predection: 0,
+            bump: 0,
+            knocked_out: 0,
+            magnitude_buckets: None,
+            range_zones: None,
+            mid_price_btc: None,
+            mid_price_sol: None,
+            payer: Pubkey::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(survived: u8, total_score: u16, avg_response_time: u32, entry_slot: u64) -> RankKey {
+        RankKey {
+            survived,
+            total_score,
+            avg_response_time,
+            first_prediction_ts: 100,
+            entry_slot,
+        }
+    }
+
+    #[test]
+    fn survival_outranks_score() {
+        let survivor = key(u8::MAX, 10, 30, 2);
+        let eliminated = key(3, 500, 1, 1);
+        assert!(survivor.ranks_before(&eliminated));
+        assert!(!eliminated.ranks_before(&survivor));
+    }
+
+    #[test]
+    fn higher_score_then_faster_response_ranks_first() {
+        assert!(key(u8::MAX, 300, 50, 2).ranks_before(&key(u8::MAX, 200, 10, 1)));
+        assert!(key(u8::MAX, 200, 10, 2).ranks_before(&key(u8::MAX, 200, 11, 1)));
+    }
+
+    #[test]
+    fn ties_fall_back_to_first_prediction_then_entry_slot() {
+        let mut early = key(u8::MAX, 200, 10, 2);
+        early.first_prediction_ts = 99;
+        assert!(early.ranks_before(&key(u8::MAX, 200, 10, 1)));
+
+        assert!(key(u8::MAX, 200, 10, 1).ranks_before(&key(u8::MAX, 200, 10, 2)));
+    }
+
+    #[test]
+    fn equal_keys_rank_neither_way() {
+        let a = key(u8::MAX, 200, 10, 1);
+        assert!(!a.ranks_before(&a));
+    }
+
+    fn profile() -> PlayerProfile {
+        PlayerProfile {
+            player: Pubkey::new_unique(),
+            games_joined: 0,
+            games_played: 0,
+            games_won: 0,
+            total_score: 0,
+            best_rank: None,
+            prizes_claimed: 0,
+            total_winnings: 0,
+            created_at: 0,
+            last_joined_at: 0,
+            bump: 255,
+            _reserved: [0; 32],
+        }
+    }
+
+    #[test]
+    fn leaving_takes_back_the_profile_join() {
+        let mut profile = profile();
+        profile.record_join(100).unwrap();
+        profile.record_join(200).unwrap();
+        profile.record_leave().unwrap();
+        assert_eq!(profile.games_joined, 1);
+        assert_eq!(profile.last_joined_at, 200);
+
+        profile.record_leave().unwrap();
+        assert!(profile.record_leave().is_err());
+    }
+
+    #[test]
+    fn ranked_games_keep_the_best_rank_and_count_wins() {
+        let mut profile = profile();
+        profile.record_ranked(Some(4), 300, false).unwrap();
+        profile.record_ranked(None, 100, false).unwrap();
+        profile.record_ranked(Some(1), 500, true).unwrap();
+        profile.record_ranked(Some(7), 200, false).unwrap();
+        assert_eq!(profile.games_played, 4);
+        assert_eq!(profile.games_won, 1);
+        assert_eq!(profile.total_score, 1_100);
+        assert_eq!(profile.best_rank, Some(1));
+    }
+
+    #[test]
+    fn claimed_prizes_add_up() {
+        let mut profile = profile();
+        profile.record_prize(1_000).unwrap();
+        profile.record_prize(250).unwrap();
+        assert_eq!(profile.prizes_claimed, 2);
+        assert_eq!(profile.total_winnings, 1_250);
+    }
+
+    #[test]
+    fn leaving_takes_back_the_player_and_refund() {
+        let mut game = GameState::test_game();
+        game.record_join(100).unwrap();
+        game.record_join(90).unwrap();
+        game.record_leave(100).unwrap();
+        assert_eq!(game.total_player, 1);
+        assert_eq!(game.prize_pool, 90);
+    }
+
+    #[test]
+    fn entry_slots_are_not_reused_after_a_leave() {
+        let mut game = GameState::test_game();
+        assert_eq!(game.record_join(100).unwrap(), 1);
+        assert_eq!(game.record_join(100).unwrap(), 2);
+        game.record_leave(100).unwrap();
+        assert_eq!(game.record_join(100).unwrap(), 3);
+        assert_eq!(game.total_player, 2);
+    }
+
+    #[test]
+    fn leaving_an_empty_game_fails() {
+        let mut game = GameState::test_game();
+        assert!(game.record_leave(0).is_err());
+
+        game.record_join(100).unwrap();
+        assert!(game.record_leave(101).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use vault::program::Vault;
+use vault::state::Treasury;
+
+use crate::constants::*;
+use crate::errors::GameError;
+use crate::events::{GuaranteeCovered, TreasuryCoverReturned};
+use crate::state::*;
+
+/// Top the prize pool up to the game's guaranteed prize from the vault
+/// treasury of the game's mint. The GameState PDA signs the vault CPI, and
+/// the pool is credited with what actually arrived. Games without a
+/// guarantee, or whose pool already meets it, are skipped. Returns the
+/// amount covered.
+pub fn cover_guaranteed_prize<'info>(
+    game_state: &mut Account<'info, GameState>,
+    prize_pool_token_account: Option<&mut InterfaceAccount<'info, TokenAccount>>,
+    treasury: Option<&Account<'info, Treasury>>,
+    treasury_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_mint: Option<&InterfaceAccount<'info, Mint>>,
+    token_program: Option<&Interface<'info, TokenInterface>>,
+    vault_program: Option<&Program<'info, Vault>>,
+) -> Result<u64> {
+    let shortfall = game_state.guaranteed_prize.saturating_sub(game_state.prize_pool);
+    if shortfall == 0 {
+        return Ok(0);
+    }
+
+    let (pool_account, treasury, treasury_account, token_mint, token_program, vault_program) = match (
+        prize_pool_token_account,
+        treasury,
+        treasury_token_account,
+        token_mint,
+        token_program,
+        vault_program,
+    ) {
+        (Some(pool), Some(treasury), Some(treasury_account), Some(mint), Some(program), Some(vault)) => {
+            (pool, treasury, treasury_account, mint, program, vault)
+        }
+        _ => return err!(GameError::InvalidTreasuryAccount),
+    };
+
+    // the vault checks the treasury PDA and its token account
+    require_keys_eq!(treasury.token_mint, game_state.token_mint, GameError::InvalidTokenMint);
+    require_keys_eq!(token_mint.key(), game_state.token_mint, GameError::InvalidTokenMint);
+    require_keys_eq!(pool_account.mint, game_state.token_mint, GameError::InvalidTokenMint);
+
+    // Pool is owned by the game_state PDA, which also signs for the cover
+    let game_id_bytes = game_state.game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    let balance_before = pool_account.amount;
+
+    vault::cpi::cover_game_prize(
+        CpiContext::new_with_signer(
+            vault_program.to_account_info(),
+            vault::cpi::accounts::CoverGamePrize {
+                game_authority: game_state.to_account_info(),
+                treasury: treasury.to_account_info(),
+                treasury_token_account: treasury_account.to_account_info(),
+                prize_pool_token_account: pool_account.to_account_info(),
+                token_mint: token_mint.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        game_state.game_id,
+        shortfall,
+    )?;
+
+    // transfer fee mints deliver less than was sent
+    pool_account.reload()?;
+    let received = pool_account.amount
+        .checked_sub(balance_before)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    game_state.treasury_cover = received;
+    game_state.prize_pool = game_state
+        .prize_pool
+        .checked_add(received)
+        .ok_or(GameError::ArithmeticOverflow)?;
+
+    msg!("Guaranteed prize covered by treasury: {}", received);
+
+    emit!(GuaranteeCovered {
+        game_id: game_state.game_id,
+        amount: received,
+        prize_pool: game_state.prize_pool,
+    });
+
+    Ok(received)
+}
+
+/// Give a cancelled game's treasury cover back to the vault treasury it came
+/// from. Refunds only pay back entries and sponsorships, so the cover is what
+/// is left in the pool once every refund was claimed, capped at what the
+/// treasury put in. The GameState PDA signs as the funder. Returns the amount
+/// sent back.
+pub fn return_treasury_cover<'info>(
+    game_state: &Account<'info, GameState>,
+    prize_pool_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    treasury: Option<&Account<'info, Treasury>>,
+    treasury_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    vault_program: Option<&Program<'info, Vault>>,
+) -> Result<u64> {
+    let amount = cover_to_return(game_state, prize_pool_token_account.amount);
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let (treasury, treasury_account, vault_program) = match (treasury, treasury_token_account, vault_program) {
+        (Some(treasury), Some(treasury_account), Some(vault)) => (treasury, treasury_account, vault),
+        _ => return err!(GameError::InvalidTreasuryAccount),
+    };
+
+    // the vault checks the treasury PDA and its token account
+    require_keys_eq!(treasury.token_mint, game_state.token_mint, GameError::InvalidTokenMint);
+
+    // Pool is owned by the game_state PDA
+    let game_id_bytes = game_state.game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[GAME_SEED, &game_id_bytes, &[game_state.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    vault::cpi::fund_treasury(
+        CpiContext::new_with_signer(
+            vault_program.to_account_info(),
+            vault::cpi::accounts::FundTreasury {
+                funder: game_state.to_account_info(),
+                treasury: treasury.to_account_info(),
+                treasury_token_account: treasury_account.to_account_info(),
+                funder_token_account: prize_pool_token_account.to_account_info(),
+                token_mint: token_mint.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    prize_pool_token_account.reload()?;
+
+    msg!("Treasury cover returned: {}", amount);
+
+    emit!(TreasuryCoverReturned {
+        game_id: game_state.game_id,
+        amount,
+    });
+
+    Ok(amount)
+}
+
+/// Treasury cover a closing game owes back: only cancelled games return it,
+/// and never more than the pool still holds
+pub fn cover_to_return(game_state: &GameState, pool_balance: u64) -> u64 {
+    match game_state.status {
+        GameStatus::Cancelled => game_state.treasury_cover.min(pool_balance),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_cancelled_games_return_their_cover() {
+        let mut game = GameState::test_game();
+        game.treasury_cover = 500;
+        game.status = GameStatus::Completed;
+        assert_eq!(cover_to_return(&game, 800), 0);
+
+        game.status = GameStatus::Cancelled;
+        assert_eq!(cover_to_return(&game, 800), 500);
+    }
+
+    #[test]
+    fn returned_cover_is_capped_at_the_pool_balance() {
+        let mut game = GameState::test_game();
+        game.status = GameStatus::Cancelled;
+        game.treasury_cover = 500;
+        assert_eq!(cover_to_return(&game, 420), 420);
+        assert_eq!(cover_to_return(&game, 0), 0);
+    }
+
+    #[test]
+    fn vault_accepts_this_programs_game_pdas() {
+        assert_eq!(vault::constants::GAME_PROGRAM_ID, crate::ID);
+        assert_eq!(vault::constants::GAME_SEED, GAME_SEED);
+    }
+}
@@ -22,4 +22,8 @@ pub const PYTH_STATUS_AUCTION: u32 = 3;
 pub const PYTH_STATUS_UNKNOWN: u32 = 0;
 
 /// Micro-dollars multiplier for price normalization
-pub const MICRO_DOLLARS_MULTIPLIER: u64 = 1_000_000;
\ No newline at end of file
+pub const MICRO_DOLLARS_MULTIPLIER: u64 = 1_000_000;
+
+/// How long a snapshot is kept before anyone can close it: 24 hours
+/// Comfortably past the round it was taken for resolving
+pub const SNAPSHOT_RETENTION_SECONDS: i64 = 86_400;
\ No newline at end of file
@@ -40,4 +40,7 @@ pub enum OracleError {
     
     #[msg("Invalid confidence threshold")]
     InvalidConfidenceThreshold,
+    
+    #[msg("Price snapshot is still inside its retention window")]
+    SnapshotStillRetained,
 }
\ No newline at end of file
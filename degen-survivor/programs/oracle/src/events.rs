@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AssetType, PriceStatus, SnapshotType};
+
+/// Oracle configuration created
+#[event]
+pub struct OracleInitialized {
+    pub admin: Pubkey,
+    pub btc_price_feed: Pubkey,
+    pub sol_price_feed: Pubkey,
+    pub staleness_threshold: i64,
+    pub confidence_threshold: u64,
+    pub min_publishers: u8,
+    pub keeper: Pubkey,
+}
+
+/// Oracle configuration changed (full config after the update)
+#[event]
+pub struct OracleConfigUpdated {
+    pub admin: Pubkey,
+    pub btc_price_feed: Pubkey,
+    pub sol_price_feed: Pubkey,
+    pub staleness_threshold: i64,
+    pub confidence_threshold: u64,
+    pub min_publishers: u8,
+    pub emergency_pause: bool,
+    pub keeper: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Pyth price stored as a round snapshot
+#[event]
+pub struct PriceSnapshotStored {
+    pub game_id: u64,
+    pub round_number: u8,
+    pub asset_type: AssetType,
+    pub snapshot_type: SnapshotType,
+    pub price: i64,
+    pub exponent: i32,
+    pub confidence: u64,
+    pub price_normalized: u64,
+    pub publish_time: i64,
+    pub snapshot_time: i64,
+    pub status: PriceStatus,
+}
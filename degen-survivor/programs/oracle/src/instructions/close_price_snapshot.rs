@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::OracleError;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, round_number: u8, asset_type: AssetType, snapshot_type: SnapshotType)]
+pub struct ClosePriceSnapshot<'info> {
+    /// Anyone can close an expired snapshot
+    pub caller: Signer<'info>,
+
+    /// PriceSnapshot account (closed)
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            PRICE_SNAPSHOT_SEED,
+            game_id.to_le_bytes().as_ref(),
+            &[round_number],
+            &[asset_type as u8],
+            &[snapshot_type as u8],
+        ],
+        bump = price_snapshot.bump,
+    )]
+    pub price_snapshot: Account<'info, PriceSnapshot>,
+
+    /// Payer of fetch_and_store, receives the rent
+    #[account(mut, address = price_snapshot.payer @ OracleError::Unauthorized)]
+    pub payer: SystemAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<ClosePriceSnapshot>,
+    game_id: u64,
+    round_number: u8,
+    asset_type: AssetType,
+    snapshot_type: SnapshotType,
+) -> Result<()> {
+    let price_snapshot = &ctx.accounts.price_snapshot;
+    let clock = Clock::get()?;
+
+    let expires_at = price_snapshot
+        .snapshot_time
+        .checked_add(SNAPSHOT_RETENTION_SECONDS)
+        .ok_or(OracleError::ArithmeticOverflow)?;
+    require!(
+        clock.unix_timestamp >= expires_at,
+        OracleError::SnapshotStillRetained
+    );
+
+    msg!("Price snapshot closed!");
+    msg!("Game ID: {}", game_id);
+    msg!("Round: {}", round_number);
+    msg!("Asset: {:?}", asset_type);
+    msg!("Type: {:?}", snapshot_type);
+
+    Ok(())
+}
@@ -3,12 +3,14 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 use crate::constants::*;
 use crate::errors::OracleError;
+use crate::events::PriceSnapshotStored;
 use crate::state::*;
 
 #[derive(Accounts)]
 #[instruction(game_id: u64, round_number: u8, asset_type: AssetType, snapshot_type: SnapshotType)]
 pub struct FetchAndStore<'info> {
-    /// Payer for account creation
+    /// Oracle keeper, pays for account creation.
+    /// Snapshots decide round outcomes, so nobody else may pick their timing
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -16,15 +18,16 @@ pub struct FetchAndStore<'info> {
     #[account(
         seeds = [ORACLE_CONFIG_SEED],
         bump = oracle_config.bump,
+        constraint = oracle_config.keeper == payer.key() @ OracleError::Unauthorized,
     )]
     pub oracle_config: Account<'info, OracleConfig>,
 
     /// Pyth price account (external account from Pyth)
     pub pyth_price_account: Account<'info, PriceUpdateV2>,
 
-    /// PriceSnapshot account (create)
+    /// PriceSnapshot account (create, or overwrite once expired)
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = PriceSnapshot::SIZE,
         seeds = [
@@ -56,6 +59,20 @@ pub fn handler(
     
     // Check if oracle is paused
     require!(!oracle_config.emergency_pause, OracleError::OraclePaused);
+
+    // An existing snapshot can only be replaced once its retention expired,
+    // i.e. it belongs to a closed game whose game_id got reused
+    let is_new = price_snapshot.payer == Pubkey::default();
+    if !is_new {
+        let retained_until = price_snapshot
+            .snapshot_time
+            .checked_add(SNAPSHOT_RETENTION_SECONDS)
+            .ok_or(OracleError::ArithmeticOverflow)?;
+        require!(
+            clock.unix_timestamp >= retained_until,
+            OracleError::SnapshotAlreadyExists
+        );
+    }
     
     // Verify correct price feed
     let expected_feed = match asset_type {
@@ -132,6 +149,10 @@ pub fn handler(
     price_snapshot.staleness = staleness;
     price_snapshot.status = status;
     price_snapshot.bump = ctx.bumps.price_snapshot;
+    // rent stays with whoever created the account
+    if is_new {
+        price_snapshot.payer = ctx.accounts.payer.key();
+    }
     
     msg!("Price snapshot created!");
     msg!("Game ID: {}", game_id);
@@ -143,6 +164,20 @@ pub fn handler(
     msg!("Confidence: {}", conf);
     msg!("Staleness: {}s", staleness);
     msg!("Status: {:?}", status);
+
+    emit!(PriceSnapshotStored {
+        game_id,
+        round_number,
+        asset_type,
+        snapshot_type,
+        price,
+        exponent: expo,
+        confidence: conf,
+        price_normalized,
+        publish_time,
+        snapshot_time: current_time,
+        status,
+    });
     
     Ok(())
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::OracleError;
+use crate::events::OracleInitialized;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -31,6 +32,7 @@ pub fn handler(
     staleness_threshold: i64,
     confidence_threshold: u64,
     min_publishers: u8,
+    keeper: Pubkey,
 ) -> Result<()> {
     let oracle_config = &mut ctx.accounts.oracle_config;
     let clock = Clock::get()?;
@@ -56,6 +58,7 @@ pub fn handler(
     oracle_config.emergency_pause = false;
     oracle_config.last_updated = clock.unix_timestamp;
     oracle_config.bump = ctx.bumps.oracle_config;
+    oracle_config.keeper = keeper;
     
     msg!("Oracle initialized!");
     msg!("Admin: {}", oracle_config.admin);
@@ -64,6 +67,17 @@ pub fn handler(
     msg!("Staleness threshold: {}s", staleness_threshold);
     msg!("Confidence threshold: {}", confidence_threshold);
     msg!("Min publishers: {}", min_publishers);
+    msg!("Keeper: {}", keeper);
+
+    emit!(OracleInitialized {
+        admin: oracle_config.admin,
+        btc_price_feed,
+        sol_price_feed,
+        staleness_threshold,
+        confidence_threshold,
+        min_publishers,
+        keeper,
+    });
     
     Ok(())
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 pub mod initialize_oracle;
 pub mod update_oracle;
 pub mod fetch_and_store;
+pub mod close_price_snapshot;
 
 pub use initialize_oracle::*;
 pub use update_oracle::*;
-pub use fetch_and_store::*;
\ No newline at end of file
+pub use fetch_and_store::*;
+pub use close_price_snapshot::*;
\ No newline at end of file
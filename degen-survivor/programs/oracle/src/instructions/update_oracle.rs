@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::OracleError;
+use crate::events::OracleConfigUpdated;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -67,6 +68,42 @@ pub fn handler(
     oracle_config.last_updated = clock.unix_timestamp;
     
     msg!("Oracle configuration updated!");
+
+    emit!(OracleConfigUpdated {
+        admin: oracle_config.admin,
+        btc_price_feed: oracle_config.btc_price_feed,
+        sol_price_feed: oracle_config.sol_price_feed,
+        staleness_threshold: oracle_config.staleness_threshold,
+        confidence_threshold: oracle_config.confidence_threshold,
+        min_publishers: oracle_config.min_publishers,
+        emergency_pause: oracle_config.emergency_pause,
+        keeper: oracle_config.keeper,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn set_keeper(ctx: Context<UpdateOracle>, keeper: Pubkey) -> Result<()> {
+    let oracle_config = &mut ctx.accounts.oracle_config;
+    let clock = Clock::get()?;
+    
+    oracle_config.keeper = keeper;
+    oracle_config.last_updated = clock.unix_timestamp;
+    
+    msg!("Oracle keeper updated: {}", keeper);
+
+    emit!(OracleConfigUpdated {
+        admin: oracle_config.admin,
+        btc_price_feed: oracle_config.btc_price_feed,
+        sol_price_feed: oracle_config.sol_price_feed,
+        staleness_threshold: oracle_config.staleness_threshold,
+        confidence_threshold: oracle_config.confidence_threshold,
+        min_publishers: oracle_config.min_publishers,
+        emergency_pause: oracle_config.emergency_pause,
+        keeper: oracle_config.keeper,
+        timestamp: clock.unix_timestamp,
+    });
     
     Ok(())
-}
\ No newline at end of file
+}
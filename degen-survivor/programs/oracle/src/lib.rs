@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -15,7 +16,7 @@ pub mod oracle {
     use super::*;
 
     /// Initialize oracle configuration
-    /// Sets up Pyth feed addresses and validation thresholds
+    /// Sets up Pyth feed addresses, validation thresholds and the snapshot keeper
     /// Should be called once during deployment
     pub fn initialize_oracle(
         ctx: Context<InitializeOracle>,
@@ -24,6 +25,7 @@ pub mod oracle {
         staleness_threshold: i64,
         confidence_threshold: u64,
         min_publishers: u8,
+        keeper: Pubkey,
     ) -> Result<()> {
         instructions::initialize_oracle::handler(
             ctx,
@@ -32,12 +34,13 @@ pub mod oracle {
             staleness_threshold,
             confidence_threshold,
             min_publishers,
+            keeper,
         )
     }
 
     /// Fetch price from Pyth and store validated snapshot
-    /// Called by backend at start and end of each round
-    /// Validates price quality before storing
+    /// Keeper only, at start and end of each round (and mid-round for Trend rounds)
+    /// Validates price quality before storing, replaces a snapshot only once it expired
     pub fn fetch_and_store(
         ctx: Context<FetchAndStore>,
         game_id: u64,
@@ -48,6 +51,18 @@ pub mod oracle {
         instructions::fetch_and_store::handler(ctx, game_id, round_number, asset_type, snapshot_type)
     }
 
+    /// Close a price snapshot once its retention window has passed
+    /// Permissionless, rent goes back to whoever paid for the snapshot
+    pub fn close_price_snapshot(
+        ctx: Context<ClosePriceSnapshot>,
+        game_id: u64,
+        round_number: u8,
+        asset_type: AssetType,
+        snapshot_type: SnapshotType,
+    ) -> Result<()> {
+        instructions::close_price_snapshot::handler(ctx, game_id, round_number, asset_type, snapshot_type)
+    }
+
     /// Update oracle configuration
     /// Only admin can call this
     /// Allows updating feed addresses, thresholds, or emergency pause
@@ -70,4 +85,10 @@ pub mod oracle {
             emergency_pause,
         )
     }
+
+    /// Replace the keeper allowed to store price snapshots
+    /// Only admin can call this
+    pub fn set_keeper(ctx: Context<UpdateOracle>, keeper: Pubkey) -> Result<()> {
+        instructions::update_oracle::set_keeper(ctx, keeper)
+    }
 }
\ No newline at end of file
@@ -31,13 +31,16 @@ pub struct OracleConfig {
     /// PDA bump
     pub bump: u8,
     
+    /// Only signer allowed to store price snapshots
+    pub keeper: Pubkey,
+    
     /// Reserved for future use
-    pub _reserved: [u8; 64],
+    pub _reserved: [u8; 32],
 }
 
 impl OracleConfig {
-    // 8 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 1 + 64 = 195 bytes
-    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 1 + 64;
+    // 8 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 1 + 32 + 32 = 195 bytes
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 1 + 32 + 32;
 }
 
 /// Price snapshot for a specific round
@@ -93,11 +96,14 @@ pub struct PriceSnapshot {
     
     /// PDA bump
     pub bump: u8,
+
+    /// Who paid the rent, refunded on close
+    pub payer: Pubkey,
 }
 
 impl PriceSnapshot {
-    // 8 + 8 + 1 + 1 + 1 + 8 + 8 + 4 + 8 + 8 + 4 + 4 + 8 + 8 + 1 + 1 = 81 bytes
-    pub const SIZE: usize = 8 + 8 + 1 + 1 + 1 + 8 + 8 + 4 + 8 + 8 + 4 + 4 + 8 + 8 + 1 + 1;
+    // 8 + 8 + 1 + 1 + 1 + 8 + 8 + 4 + 8 + 8 + 4 + 4 + 8 + 8 + 1 + 1 + 32 = 113 bytes
+    pub const SIZE: usize = 8 + 8 + 1 + 1 + 1 + 8 + 8 + 4 + 8 + 8 + 4 + 4 + 8 + 8 + 1 + 1 + 32;
     
     /// Calculate normalized price from raw price and exponent
     pub fn normalize_price(price: i64, exponent: i32) -> u64 {
@@ -118,11 +124,13 @@ pub enum AssetType {
     SOL,
 }
 
-/// Snapshot type (start or end of round)
+/// Snapshot type (start, middle or end of round)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum SnapshotType {
     START,
     END,
+    /// Halfway through the round, used by Trend rounds
+    MID,
 }
 
 /// Price validation status
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Prize pool set up for a completed game
+#[event]
+pub struct PrizePoolInitialized {
+    pub game_id: u64,
+    pub game_state: Pubkey,
+    pub admin: Pubkey,
+    pub total_pool: u64,
+    pub platform_fee: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::PrizeError;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct ClosePrizePool<'info> {
+    /// Admin that created the pool, receives the rent
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// PrizePool PDA (closed)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PRIZE_POOL_SEED, game_id.to_le_bytes().as_ref()],
+        bump = prize_pool.bump,
+        constraint = prize_pool.admin == admin.key() @ PrizeError::Unauthorized,
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+}
+
+pub fn handler(ctx: Context<ClosePrizePool>, game_id: u64) -> Result<()> {
+    let prize_pool = &ctx.accounts.prize_pool;
+
+    // only a record: prizes and the platform fee are settled by the game program
+    msg!("Prize pool closed!");
+    msg!("Game ID: {}", game_id);
+    msg!("Platform fee: {}", prize_pool.platform_fee);
+
+    Ok(())
+}
@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use game::constants::GAME_SEED;
+use game::instructions::distributable_pool;
+use game::state::{GameState, GameStatus};
 
 use crate::constants::*;
+use crate::errors::PrizeError;
+use crate::events::PrizePoolInitialized;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -20,9 +26,17 @@ pub struct InitializePrizePool<'info> {
     )]
     pub prize_pool: Account<'info, PrizePool>,
 
-    /// Game state account (from game program)
-    /// CHECK: We verify this matches the game_id
-    pub game_state: AccountInfo<'info>,
+    /// GameState PDA of this game in the game program
+    #[account(
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump = game_state.bump,
+        seeds::program = game::ID,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// Mint the game is played in
+    #[account(address = game_state.token_mint @ PrizeError::InvalidTokenMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// System program
     pub system_program: Program<'info, System>,
@@ -31,41 +45,48 @@ pub struct InitializePrizePool<'info> {
 pub fn handler(
     ctx: Context<InitializePrizePool>,
     game_id: u64,
-    total_pool: u64,
-    game_state_bump: u8,
 ) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
     let prize_pool = &mut ctx.accounts.prize_pool;
     let clock = Clock::get()?;
+
+    require!(
+        game_state.status == GameStatus::Completed,
+        PrizeError::GameNotCompleted
+    );
     
-    // Calculate platform fee (6% of total pool)
-    let platform_fee = (total_pool as u128)
-        .checked_mul(PLATFORM_FEE_BPS as u128)
-        .and_then(|v| v.checked_div(BPS_DIVISOR as u128))
-        .and_then(|v| u64::try_from(v).ok())
-        .unwrap_or(0);
+    // same split as finalize_leaderboard, with the game's own fee rate
+    let total_pool = game_state.prize_pool;
+    let distributable = distributable_pool(total_pool, game_state.platform_fee_bps);
+    let platform_fee = total_pool
+        .checked_sub(distributable)
+        .ok_or(PrizeError::ArithmeticOverflow)?;
     
     // Initialize prize pool
     prize_pool.game_id = game_id;
     prize_pool.game_state = ctx.accounts.game_state.key();
     prize_pool.total_pool = total_pool;
-    prize_pool.distributed_amount = 0;
     prize_pool.platform_fee = platform_fee;
-    prize_pool.platform_fee_collected = false;
-    prize_pool.claims_processed = 0;
-    prize_pool.total_winners = TOTAL_WINNERS;
-    prize_pool.fully_distributed = false;
     prize_pool.created_at = clock.unix_timestamp;
-    prize_pool.first_claim_at = None;
-    prize_pool.last_claim_at = None;
     prize_pool.admin = ctx.accounts.admin.key();
-    prize_pool.game_state_bump = game_state_bump;
     prize_pool.bump = ctx.bumps.prize_pool;
+    prize_pool.token_mint = ctx.accounts.token_mint.key();
     
     msg!("Prize pool initialized!");
     msg!("Game ID: {}", game_id);
     msg!("Total pool: {}", total_pool);
-    msg!("Platform fee: {} ({}%)", platform_fee, PLATFORM_FEE_BPS / 100);
-    msg!("Distributable pool: {}", total_pool - platform_fee);
+    msg!("Token mint: {}", prize_pool.token_mint);
+    msg!("Platform fee: {} ({} bps)", platform_fee, game_state.platform_fee_bps);
+    msg!("Distributable pool: {}", distributable);
+
+    emit!(PrizePoolInitialized {
+        game_id,
+        game_state: prize_pool.game_state,
+        admin: prize_pool.admin,
+        total_pool,
+        platform_fee,
+        timestamp: clock.unix_timestamp,
+    });
     
     Ok(())
 }
\ No newline at end of file
@@ -1,7 +1,5 @@
 pub mod initialize_pool;
-pub mod claim_prize;
-pub mod collect_platform_fee;
+pub mod close_prize_pool;
 
 pub use initialize_pool::*;
-pub use claim_prize::*;
-pub use collect_platform_fee::*;
\ No newline at end of file
+pub use close_prize_pool::*;
\ No newline at end of file
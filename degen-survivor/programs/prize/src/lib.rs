@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -15,35 +16,20 @@ pub mod prize {
 
     /// Initialize prize pool for a game
     /// Called by backend after game is completed
-    /// Sets up prize distribution accounts
+    /// Records the game's pool and platform fee from its GameState
     pub fn initialize_prize_pool(
         ctx: Context<InitializePrizePool>,
         game_id: u64,
-        total_pool: u64,
-        game_state_bump: u8,
     ) -> Result<()> {
-        instructions::initialize_pool::handler(ctx, game_id, total_pool, game_state_bump)
+        instructions::initialize_pool::handler(ctx, game_id)
     }
 
-    /// Player claims their prize
-    /// Can only be called by winners (rank 1-10)
-    /// Transfers DEGEN tokens to winner's account
-    pub fn claim_prize(
-        ctx: Context<ClaimPrize>,
+    /// Admin closes a prize pool record
+    /// Prizes are claimed and the platform fee swept by the game program
+    pub fn close_prize_pool(
+        ctx: Context<ClosePrizePool>,
         game_id: u64,
-        rank: u16,
-        prize_amount: u64,
     ) -> Result<()> {
-        instructions::claim_prize::handler(ctx, game_id, rank, prize_amount)
-    }
-
-    /// Admin collects platform fee from a game
-    /// 6% of total prize pool
-    /// Can only be called once per game
-    pub fn collect_platform_fee(
-        ctx: Context<CollectPlatformFee>,
-        game_id: u64,
-    ) -> Result<()> {
-        instructions::collect_platform_fee::handler(ctx, game_id)
+        instructions::close_prize_pool::handler(ctx, game_id)
     }
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 /// PDA seed for GlobalVault
 pub const GLOBAL_VAULT_SEED: &[u8] = b"global-vault";
 
@@ -7,6 +9,18 @@ pub const USER_VAULT_SEED: &[u8] = b"user-vault";
 /// PDA seed for fee collector account
 pub const FEE_COLLECTOR_SEED: &[u8] = b"fee-collector";
 
+/// PDA seed prefix for a Treasury, one per mint
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// PDA seed prefix for a treasury's token account, owned by the Treasury PDA
+pub const TREASURY_TOKEN_SEED: &[u8] = b"treasury-tokens";
+
+/// Game program, its GameState PDAs sign for treasury cover
+pub const GAME_PROGRAM_ID: Pubkey = pubkey!("8CLxjoAivuuxrFNK8aanU2f6Nw7L6tBT2xEgNNoUMmNE");
+
+/// PDA seed prefix of a GameState in the game program
+pub const GAME_SEED: &[u8] = b"game";
+
 /// Default conversion rate: 1 SOL = 10,000 DEGEN tokens
 pub const DEFAULT_CONVERSION_RATE: u64 = 10_000;
 
@@ -47,4 +47,13 @@ pub enum VaultError {
 
     #[msg("Token account does not belong to user")]
     InvalidTokenAccount,
+
+    #[msg("Signer is not the GameState of this game")]
+    InvalidGameAuthority,
+
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Transfer fee mints must use the Token-2022 program")]
+    TransferFeeRequiresToken2022,
 }
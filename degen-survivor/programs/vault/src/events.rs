@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+/// GlobalVault created
+#[event]
+pub struct VaultInitialized {
+    pub admin: Pubkey,
+    pub token_mint: Pubkey,
+    pub conversion_rate: u64,
+    pub withdrawal_fee_bps: u16,
+    // 0 when the mint has no transfer fee
+    pub transfer_fee_bps: u16,
+}
+
+/// SOL deposited and DEGEN minted
+#[event]
+pub struct Deposited {
+    pub user: Pubkey,
+    pub sol_amount: u64,
+    pub degen_amount: u64,
+    pub total_degen_balance: u64,
+}
+
+/// Withdrawal queued behind the timelock
+#[event]
+pub struct WithdrawalRequested {
+    pub user: Pubkey,
+    pub degen_amount: u64,
+    pub requested_at: i64,
+    pub unlock_ts: i64,
+}
+
+/// DEGEN burned and SOL returned
+#[event]
+pub struct WithdrawalExecuted {
+    pub user: Pubkey,
+    pub degen_amount: u64,
+    pub sol_returned: u64,
+    pub fee_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Platform fee for a game paid out in SOL
+#[event]
+pub struct GameFeeCollected {
+    pub game_id: u64,
+    pub platform_wallet: Pubkey,
+    pub fee_degen: u64,
+    pub fee_sol: u64,
+}
+
+/// Vault paused or unpaused
+#[event]
+pub struct VaultPauseSet {
+    pub paused: bool,
+}
+
+/// DEGEN per SOL rate changed
+#[event]
+pub struct ConversionRateUpdated {
+    pub conversion_rate: u64,
+}
+
+/// Withdrawal fee changed
+#[event]
+pub struct WithdrawalFeeUpdated {
+    pub withdrawal_fee_bps: u16,
+}
+
+/// Admin authority handed over
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+/// Treasury opened for a mint
+#[event]
+pub struct TreasuryInitialized {
+    pub treasury: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+}
+
+/// Tokens added to a treasury
+#[event]
+pub struct TreasuryFunded {
+    pub treasury: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_funded: u64,
+}
+
+/// Admin took tokens back out of a treasury
+#[event]
+pub struct TreasuryWithdrawn {
+    pub treasury: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+}
+
+/// Treasury topped up a game's prize pool to its guarantee
+#[event]
+pub struct GamePrizeCovered {
+    pub game_id: u64,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub total_covered: u64,
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::VaultError;
+use crate::events::*;
 use crate::state::*;
 
 /// Pause/unpause vault operations
@@ -25,6 +26,8 @@ pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
     global_vault.paused = paused;
     
     msg!("Vault paused status: {}", paused);
+
+    emit!(VaultPauseSet { paused });
     Ok(())
 }
 
@@ -51,6 +54,8 @@ pub fn update_conversion_rate(ctx: Context<UpdateConversionRate>, new_rate: u64)
     global_vault.conversion_rate = new_rate;
     
     msg!("Conversion rate updated to: {} DEGEN per SOL", new_rate);
+
+    emit!(ConversionRateUpdated { conversion_rate: new_rate });
     Ok(())
 }
 
@@ -80,6 +85,8 @@ pub fn update_withdrawal_fee(ctx: Context<UpdateWithdrawalFee>, new_fee_bps: u16
     global_vault.withdrawal_fee_bps = new_fee_bps;
     
     msg!("Withdrawal fee updated to: {}%", new_fee_bps / 100);
+
+    emit!(WithdrawalFeeUpdated { withdrawal_fee_bps: new_fee_bps });
     Ok(())
 }
 
@@ -105,8 +112,14 @@ pub struct TransferAdmin<'info> {
 
 pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
     let global_vault = &mut ctx.accounts.global_vault;
+    let previous_admin = global_vault.admin;
     global_vault.admin = ctx.accounts.new_admin.key();
     
     msg!("Admin transferred to: {}", global_vault.admin);
+
+    emit!(AdminTransferred {
+        previous_admin,
+        new_admin: global_vault.admin,
+    });
     Ok(())
 }
\ No newline at end of file
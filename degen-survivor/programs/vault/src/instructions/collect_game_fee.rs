@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::VaultError;
+use crate::events::*;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -81,6 +82,13 @@ pub fn handler(
     msg!("Platform fee (DEGEN equivalent): {}", platform_fee_degen);
     msg!("Platform fee (SOL): {} lamports", platform_fee_sol);
     msg!("Platform wallet: {}", ctx.accounts.platform_wallet.key());
+
+    emit!(GameFeeCollected {
+        game_id,
+        platform_wallet: ctx.accounts.platform_wallet.key(),
+        fee_degen: platform_fee_degen,
+        fee_sol: platform_fee_sol,
+    });
     
     Ok(())
 }
\ No newline at end of file
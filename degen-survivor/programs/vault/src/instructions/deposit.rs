@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -33,16 +34,16 @@ pub struct Deposit<'info> {
         mut,
         address = global_vault.token_mint
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         token::mint = token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>
+    pub token_program: Interface<'info, TokenInterface>
 }
 
 pub fn handler(ctx: Context<Deposit>, sol_amount: u64) -> Result<()> {
@@ -99,7 +100,7 @@ pub fn handler(ctx: Context<Deposit>, sol_amount: u64) -> Result<()> {
     let signer_seeds = &[&seeds[..]];
 
     // Mint DEGEN tokens to user's token account
-    let mint_to_accounts = anchor_spl::token::MintTo {
+    let mint_to_accounts = anchor_spl::token_interface::MintTo {
         mint: ctx.accounts.token_mint.to_account_info(),
         to: ctx.accounts.user_token_account.to_account_info(),
         authority: global_vault.to_account_info()
@@ -111,7 +112,7 @@ pub fn handler(ctx: Context<Deposit>, sol_amount: u64) -> Result<()> {
         signer_seeds
     );
 
-    anchor_spl::token::mint_to(cpi_ctx, degen_amount)?;
+    anchor_spl::token_interface::mint_to(cpi_ctx, degen_amount)?;
 
     // Update global vault balances
     global_vault.total_sol_deposited = global_vault
@@ -140,6 +141,13 @@ pub fn handler(ctx: Context<Deposit>, sol_amount: u64) -> Result<()> {
     msg!("User: {}", ctx.accounts.user.key());
     msg!("SOL deposited: {} lamports", sol_amount);
     msg!("DEGEN minted: {}", degen_amount);
+
+    emit!(Deposited {
+        user: ctx.accounts.user.key(),
+        sol_amount,
+        degen_amount,
+        total_degen_balance: user_vault.total_degen_balance,
+    });
     
     Ok(())
 }
\ No newline at end of file
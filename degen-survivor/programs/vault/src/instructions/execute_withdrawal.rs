@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
 
 use crate::constants::*;
 use crate::errors::VaultError;
+use crate::events::*;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -33,7 +34,7 @@ pub struct ExecuteWithdrawal<'info> {
         mut,
         address = global_vault.token_mint,
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// User's DEGEN token account (ATA) - tokens will be burned from here
     #[account(
@@ -41,7 +42,7 @@ pub struct ExecuteWithdrawal<'info> {
         token::mint = token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Fee collector account (receives 5% of SOL)
     /// CHECK: This is the admin's account for collecting fees
@@ -49,7 +50,7 @@ pub struct ExecuteWithdrawal<'info> {
     pub fee_collector: AccountInfo<'info>,
 
     /// SPL Token Program
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     
     /// System Program
     pub system_program: Program<'info, System>,
@@ -101,7 +102,7 @@ pub fn handler(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
     let seeds = &[GLOBAL_VAULT_SEED, &[global_vault.bump]];
     let signer = &[&seeds[..]];
     
-    token::burn(
+    token_interface::burn(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             Burn {
@@ -165,6 +166,14 @@ pub fn handler(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
     msg!("DEGEN burned: {}", degen_amount);
     msg!("SOL returned to user: {} lamports", user_receives);
     msg!("Fee collected: {} lamports", fee_amount);
+
+    emit!(WithdrawalExecuted {
+        user: ctx.accounts.user.key(),
+        degen_amount,
+        sol_returned: user_receives,
+        fee_amount,
+        timestamp: clock.unix_timestamp,
+    });
     
     Ok(())
 }
\ No newline at end of file
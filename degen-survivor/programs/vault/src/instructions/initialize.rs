@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
-use::anchor_spl::token::{Mint,Token,TokenAccount};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{
+    self, InitializeMint2, TokenInterface, TransferFeeInitialize,
+};
 
 use crate::constants::*;
+use crate::errors::VaultError;
+use crate::events::VaultInitialized;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -20,15 +27,13 @@ pub struct Initialize<'info> {
 
     pub system_program: Program<'info, System>,
 
-    #[account(
-        init,
-        payer = admin,
-        mint::decimals=TOKEN_DECIMALS,
-        mint::authority = global_vault
-    )]
-    pub token_mint : Account<'info,Mint>,
+    /// DEGEN mint (created), a fresh keypair signing its own creation.
+    /// Created by hand so Token-2022 extensions can be set up first.
+    #[account(mut)]
+    pub token_mint : Signer<'info>,
 
-    pub token_program : Program<'info,Token>,
+    /// SPL Token or Token-2022, the DEGEN mint is created under it
+    pub token_program : Interface<'info, TokenInterface>,
 
     
 
@@ -36,10 +41,13 @@ pub struct Initialize<'info> {
 
 
 
-pub fn handler(ctx:Context<Initialize>)->Result<()>{
+pub fn handler(ctx:Context<Initialize>, transfer_fee: Option<TransferFeeParams>)->Result<()>{
+    create_token_mint(&ctx, transfer_fee.as_ref())?;
+
     let global_vault = &mut ctx.accounts.global_vault;
     
     global_vault.admin = ctx.accounts.admin.key();
+    global_vault.token_mint = ctx.accounts.token_mint.key();
     global_vault.total_sol_deposited=0;
     global_vault.total_sol_withdrawal=0;
     global_vault.current_sol_balance = 0;
@@ -54,5 +62,79 @@ pub fn handler(ctx:Context<Initialize>)->Result<()>{
     msg!("Token Mint: {}", global_vault.token_mint);
     msg!("Conversion Rate: {} DEGEN per SOL", global_vault.conversion_rate);
     msg!("Withdrawal Fee: {}%", global_vault.withdrawal_fee_bps / 100);
+    msg!("Transfer Fee: {:?}", transfer_fee);
+
+    emit!(VaultInitialized {
+        admin: global_vault.admin,
+        token_mint: global_vault.token_mint,
+        conversion_rate: global_vault.conversion_rate,
+        withdrawal_fee_bps: global_vault.withdrawal_fee_bps,
+        transfer_fee_bps: transfer_fee.map_or(0, |fee| fee.basis_points),
+    });
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Create the DEGEN mint with the global vault as mint authority.
+/// A transfer fee needs Token-2022, the admin is its config and withdraw authority.
+fn create_token_mint(ctx: &Context<Initialize>, transfer_fee: Option<&TransferFeeParams>) -> Result<()> {
+    let token_program = &ctx.accounts.token_program;
+    let token_mint = ctx.accounts.token_mint.to_account_info();
+    let admin = ctx.accounts.admin.key();
+
+    let extensions = match transfer_fee {
+        Some(fee) => {
+            require_keys_eq!(
+                token_program.key(),
+                anchor_spl::token_2022::ID,
+                VaultError::TransferFeeRequiresToken2022
+            );
+            require!(
+                fee.basis_points as u64 <= BPS_DIVISOR,
+                VaultError::FeeExceedsMaximum
+            );
+            vec![ExtensionType::TransferFeeConfig]
+        }
+        None => vec![],
+    };
+    let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+
+    system_program::create_account(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.admin.to_account_info(),
+                to: token_mint.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &token_program.key(),
+    )?;
+
+    // extensions are set up before the mint is initialized
+    if let Some(fee) = transfer_fee {
+        token_interface::transfer_fee_initialize(
+            CpiContext::new(
+                token_program.to_account_info(),
+                TransferFeeInitialize {
+                    token_program_id: token_program.to_account_info(),
+                    mint: token_mint.clone(),
+                },
+            ),
+            Some(&admin),
+            Some(&admin),
+            fee.basis_points,
+            fee.maximum_fee,
+        )?;
+    }
+
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program.to_account_info(),
+            InitializeMint2 { mint: token_mint },
+        ),
+        TOKEN_DECIMALS,
+        &ctx.accounts.global_vault.key(),
+        None,
+    )
+}
@@ -4,10 +4,12 @@ pub mod request_withdrawal;
 pub mod execute_withdrawal;
 pub mod collect_game_fee;
 pub mod admin;
+pub mod treasury;
 
 pub use initialize::*;
 pub use deposit::*;
 pub use request_withdrawal::*;
 pub use execute_withdrawal::*;
 pub use collect_game_fee::*;
-pub use admin::*;   
\ No newline at end of file
+pub use admin::*;
+pub use treasury::*;   
\ No newline at end of file
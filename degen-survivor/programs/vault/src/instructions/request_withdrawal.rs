@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
 use crate::state::*;
 
 
@@ -69,5 +70,12 @@ pub fn handler (ctx:Context<RequestWithdrawal>,degen_amount:u64)-> Result<()>{
     msg!("User: {}", ctx.accounts.user.key());
     msg!("DEGEN amount: {}", degen_amount);
     msg!("Unlock time: {} (in 24 hours)", user_vault.withdrawal_unlock_ts);
+
+    emit!(WithdrawalRequested {
+        user: ctx.accounts.user.key(),
+        degen_amount,
+        requested_at: user_vault.withdrawal_requested_at,
+        unlock_ts: user_vault.withdrawal_unlock_ts,
+    });
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,307 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::VaultError;
+use crate::events::*;
+use crate::state::*;
+
+/// Open the treasury of a mint, admin only
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    /// Admin authority
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// GlobalVault PDA
+    #[account(
+        seeds = [GLOBAL_VAULT_SEED],
+        bump = global_vault.bump,
+        constraint = global_vault.admin == admin.key() @ VaultError::Unauthorized,
+    )]
+    pub global_vault: Account<'info, GlobalVault>,
+
+    /// Treasury PDA (created)
+    #[account(
+        init,
+        payer = admin,
+        space = Treasury::SIZE,
+        seeds = [TREASURY_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Treasury token account (created), owned by the Treasury PDA
+    #[account(
+        init,
+        payer = admin,
+        seeds = [TREASURY_TOKEN_SEED, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+
+    treasury.token_mint = ctx.accounts.token_mint.key();
+    treasury.token_account = ctx.accounts.treasury_token_account.key();
+    treasury.total_funded = 0;
+    treasury.total_withdrawn = 0;
+    treasury.total_covered = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Treasury initialized!");
+    msg!("Token mint: {}", treasury.token_mint);
+
+    emit!(TreasuryInitialized {
+        treasury: treasury.key(),
+        token_mint: treasury.token_mint,
+        token_account: treasury.token_account,
+    });
+    Ok(())
+}
+
+/// Add tokens to a treasury, anyone can fund it
+#[derive(Accounts)]
+pub struct FundTreasury<'info> {
+    pub funder: Signer<'info>,
+
+    /// Treasury PDA
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, token_mint.key().as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut, address = treasury.token_account @ VaultError::InvalidTokenAccount)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = funder,
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn fund_treasury(ctx: Context<FundTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+
+    let balance_before = ctx.accounts.treasury_token_account.amount;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    // transfer fee mints deliver less than was sent
+    ctx.accounts.treasury_token_account.reload()?;
+    let received = ctx.accounts.treasury_token_account.amount
+        .checked_sub(balance_before)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_funded = treasury.total_funded
+        .checked_add(received)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+
+    msg!("Treasury funded: {}", received);
+
+    emit!(TreasuryFunded {
+        treasury: treasury.key(),
+        funder: ctx.accounts.funder.key(),
+        amount: received,
+        total_funded: treasury.total_funded,
+    });
+    Ok(())
+}
+
+/// Take tokens back out of a treasury, admin only
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    /// Admin authority
+    pub admin: Signer<'info>,
+
+    /// GlobalVault PDA
+    #[account(
+        seeds = [GLOBAL_VAULT_SEED],
+        bump = global_vault.bump,
+        constraint = global_vault.admin == admin.key() @ VaultError::Unauthorized,
+    )]
+    pub global_vault: Account<'info, GlobalVault>,
+
+    /// Treasury PDA
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, token_mint.key().as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut, address = treasury.token_account @ VaultError::InvalidTokenAccount)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = admin,
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+    require!(
+        ctx.accounts.treasury_token_account.amount >= amount,
+        VaultError::InsufficientBalance
+    );
+
+    transfer_from_treasury(
+        &ctx.accounts.treasury,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.admin_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_withdrawn = treasury.total_withdrawn
+        .checked_add(amount)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+
+    msg!("Treasury withdrawn: {}", amount);
+
+    emit!(TreasuryWithdrawn {
+        treasury: treasury.key(),
+        admin: ctx.accounts.admin.key(),
+        amount,
+        total_withdrawn: treasury.total_withdrawn,
+    });
+    Ok(())
+}
+
+/// Pay a guaranteed prize shortfall into a game's pool.
+/// Only the game's own GameState PDA can sign for it, through the game program.
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CoverGamePrize<'info> {
+    /// GameState PDA of the game being covered
+    #[account(
+        seeds = [GAME_SEED, game_id.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = GAME_PROGRAM_ID,
+    )]
+    pub game_authority: Signer<'info>,
+
+    /// Treasury PDA
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, token_mint.key().as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut, address = treasury.token_account @ VaultError::InvalidTokenAccount)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Game prize pool, owned by the GameState
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = game_authority,
+    )]
+    pub prize_pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn cover_game_prize(ctx: Context<CoverGamePrize>, game_id: u64, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+    require!(
+        ctx.accounts.treasury_token_account.amount >= amount,
+        VaultError::InsufficientBalance
+    );
+
+    transfer_from_treasury(
+        &ctx.accounts.treasury,
+        &ctx.accounts.treasury_token_account,
+        &ctx.accounts.prize_pool_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_covered = treasury.total_covered
+        .checked_add(amount)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+
+    msg!("Game {} prize covered: {}", game_id, amount);
+
+    emit!(GamePrizeCovered {
+        game_id,
+        treasury: treasury.key(),
+        amount,
+        total_covered: treasury.total_covered,
+    });
+    Ok(())
+}
+
+/// Move tokens out of the treasury token account, signed by the Treasury PDA
+fn transfer_from_treasury<'info>(
+    treasury: &Account<'info, Treasury>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[TREASURY_SEED, treasury.token_mint.as_ref(), &[treasury.bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: token_mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: treasury.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        token_mint.decimals,
+    )
+}
@@ -2,10 +2,12 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::TransferFeeParams;
 
 declare_id!("5D2EFjnokFHzGeVQ2AMpdMC6SazYaashKHAzMGWRzJVd");
 
@@ -15,8 +17,11 @@ pub mod vault {
 
     /// Initialize the vault and create DEGEN token mint
     /// This should be called once during deployment
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        instructions::initialize::handler(ctx)
+    ///
+    /// # Arguments
+    /// * `transfer_fee` - Optional Token-2022 transfer fee for the mint
+    pub fn initialize(ctx: Context<Initialize>, transfer_fee: Option<TransferFeeParams>) -> Result<()> {
+        instructions::initialize::handler(ctx, transfer_fee)
     }
 
     /// Deposit SOL and mint DEGEN tokens to user
@@ -71,4 +76,27 @@ pub mod vault {
     pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
         instructions::admin::transfer_admin(ctx)
     }
+
+    /// Admin: Open the treasury of a mint
+    /// The treasury covers guaranteed prizes of promoted games
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        instructions::treasury::initialize_treasury(ctx)
+    }
+
+    /// Add tokens to a treasury
+    /// Anyone can fund it, the amount received is tracked
+    pub fn fund_treasury(ctx: Context<FundTreasury>, amount: u64) -> Result<()> {
+        instructions::treasury::fund_treasury(ctx, amount)
+    }
+
+    /// Admin: Take tokens back out of a treasury
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::treasury::withdraw_treasury(ctx, amount)
+    }
+
+    /// Top up a game's prize pool from the treasury
+    /// Signed by the game's GameState PDA through the game program at start_game
+    pub fn cover_game_prize(ctx: Context<CoverGamePrize>, game_id: u64, amount: u64) -> Result<()> {
+        instructions::treasury::cover_game_prize(ctx, game_id, amount)
+    }
 }
\ No newline at end of file
@@ -56,6 +56,39 @@ pub struct UserVault {
 }
 
 
+/// Transfer fee set on the DEGEN mint at initialize (Token-2022 only)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+
+pub struct TransferFeeParams {
+    // fee per transfer in basis points (100 bps = 1%)
+    pub basis_points : u16,
+    // cap on the fee of a single transfer, in base units
+    pub maximum_fee : u64,
+}
+
+/// Platform tokens of one mint that cover guaranteed game prizes
+#[account]
+
+pub struct Treasury {
+    pub token_mint : Pubkey,
+    // token account holding the funds, owned by this PDA
+    pub token_account : Pubkey,
+    // received from funders, after any transfer fee
+    pub total_funded : u64,
+    // taken back out by the admin
+    pub total_withdrawn : u64,
+    // paid into game prize pools
+    pub total_covered : u64,
+
+    pub bump : u8,
+
+    pub _reserved : [u8;32]
+}
+
+impl Treasury {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 32;
+}
+
 impl UserVault{
     pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 64;
 }